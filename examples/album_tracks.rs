@@ -18,6 +18,6 @@ fn main() {
         .client_credentials_manager(client_credential)
         .build();
     let birdy_uri = "spotify:album:6akEvsycLGftJxYudPjmqK";
-    let tracks = spotify.album_track(birdy_uri, Some(2), None);
+    let tracks = spotify.album_track(birdy_uri, Some(2), None, None);
     println!("{:?}", tracks);
 }
@@ -33,7 +33,7 @@ fn main() {
             let spotify = Spotify::default()
                 .client_credentials_manager(client_credential)
                 .build();
-            let context = spotify.current_playing(None);
+            let context = spotify.current_playing(None, None);
             println!("{:?}", context);
         }
         None => println!("auth failed"),
@@ -33,7 +33,7 @@ fn main() {
             let spotify = Spotify::default()
                 .client_credentials_manager(client_credential)
                 .build();
-            let playing = spotify.current_user_playing_track();
+            let playing = spotify.current_user_playing_track(None);
             println!("{:?}", playing);
         }
         None => println!("auth failed"),
@@ -32,7 +32,7 @@ fn main() {
                 .client_credentials_manager(client_credential)
                 .build();
             let query = "tania bowra";
-            let result = spotify.search_artist(query, 10, 0, Some(Country::UnitedStates));
+            let result = spotify.search_artist(query, 10, 0, Some(Country::UnitedStates), None);
             println!("search result:{:?}",result);
         }
         None => println!("auth failed"),
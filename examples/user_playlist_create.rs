@@ -38,7 +38,7 @@ fn main() {
 
             let user_id = "2257tjys2e2u2ygfke42niy2q";
             let playlist_name = "A New Playlist";
-            let playlists = spotify.user_playlist_create(user_id, playlist_name, false, None);
+            let playlists = spotify.user_playlist_create(user_id, playlist_name, false, None, None);
             println!("{:?}", playlists);
 
         }
@@ -98,7 +98,6 @@ extern crate lazy_static;
 extern crate dotenv;
 // use serde_json::Error;
 extern crate url;
-extern crate percent_encoding;
 extern crate rand;
 extern crate base64;
 
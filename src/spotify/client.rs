@@ -3,37 +3,48 @@
 use serde_json;
 use serde_json::Value;
 use serde_json::map::Map;
-use serde::de::Deserialize;
+use serde::de::{Deserialize, DeserializeOwned};
 use reqwest::Client;
 use reqwest::Method;
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap};
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, USER_AGENT, HeaderMap};
 use reqwest::StatusCode;
 use chrono::prelude::*;
 use failure;
+use itertools::Itertools;
+use rand::{self, Rng};
+use url::Url;
 
 //  built-in battery
 use std::collections::HashMap;
 use std::fmt;
-use std::io::Read;
+use std::hash::Hash;
 use std::string::String;
 use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use super::oauth2::SpotifyClientCredentials;
+use super::oauth2::{SpotifyClientCredentials, SpotifyOAuth, TokenInfo};
+use super::transport::{HttpTransport, TransportResponse};
 use super::senum::{AlbumType, Type, TimeRange, Country, RepeatState, SearchType};
-use super::model::album::{FullAlbum, FullAlbums, SimplifiedAlbum, PageSimpliedAlbums, SavedAlbum};
+use super::model::album::{Discography, FullAlbum, FullAlbums, SimplifiedAlbum, PageSimpliedAlbums, SavedAlbum};
 use super::model::page::{Page, CursorBasedPage};
 use super::model::track::{FullTrack, FullTracks, SimplifiedTrack, SavedTrack};
 use super::model::artist::{FullArtist, FullArtists, CursorPageFullArtists};
 use super::model::user::{PublicUser, PrivateUser};
 use super::model::playlist::{FullPlaylist, PlaylistTrack, SimplifiedPlaylist, FeaturedPlaylists};
 use super::model::cud_result::CUDResult;
-use super::model::playing::{Playing, PlayHistory};
+use super::model::playing::{Playing, PlayingItem, PlayHistory, Queue};
 use super::model::category::PageCategory;
-use super::model::recommend::Recommendations;
+use super::model::recommend::{GenreSeeds, Recommendations};
+use super::model::market::Markets;
 use super::model::audio::{AudioFeatures, AudioFeaturesPayload, AudioAnalysis};
 use super::model::device::DevicePayload;
 use super::model::context::{FullPlayingContext, SimplifiedPlayingContext};
 use super::model::search::{SearchAlbums, SearchArtists, SearchTracks, SearchPlaylists};
+use super::model::item::SpotifyItem;
+use super::model::show::{FullEpisode, FullEpisodes, FullShow, SavedShow, SimplifiedEpisode};
 use super::util::convert_map_to_string;
 lazy_static! {
     /// HTTP Client
@@ -44,6 +55,15 @@ lazy_static! {
 pub enum ApiError {
     Unauthorized,
     RateLimited(Option<usize>),
+    /// the playlist changed since the given snapshot_id, refetch and retry
+    SnapshotConflict,
+    /// a recommendations genre seed that Spotify doesn't know about,
+    /// detected locally before sending the request
+    InvalidGenreSeed(String),
+    /// more than the 5 recommendations seeds allowed in total
+    TooManySeeds(usize),
+    /// recommendations need at least one seed
+    NoSeeds,
     Other(u16)
 }
 impl failure::Fail for ApiError {}
@@ -58,32 +78,155 @@ impl fmt::Display for ApiError {
                     write!(f, "Exceeded API request limit")
                 }
             }
+            ApiError::SnapshotConflict => write!(f, "Playlist snapshot_id is stale, refetch the playlist and retry"),
+            ApiError::InvalidGenreSeed(genre) => write!(f, "Unknown recommendations genre seed {:?}", genre),
+            ApiError::TooManySeeds(count) => write!(f, "Recommendations take at most 5 seeds in total, got {}", count),
+            ApiError::NoSeeds => write!(f, "Recommendations need at least one seed artist, genre or track"),
             ApiError::Other(s) => write!(f, "Spotify API reported error code {}", s),
         }
     }
 }
 impl From<&reqwest::Response> for ApiError {
     fn from(response: &reqwest::Response) -> Self {
-        match response.status() {
+        ApiError::from_status(response.status(), response.headers())
+    }
+}
+impl From<&TransportResponse> for ApiError {
+    fn from(response: &TransportResponse) -> Self {
+        if response.status == StatusCode::BAD_REQUEST && ApiError::names_snapshot(&response.body) {
+            return ApiError::SnapshotConflict;
+        }
+        ApiError::from_status(response.status, &response.headers)
+    }
+}
+impl ApiError {
+    ///whether the error message of a response body is about the snapshot_id
+    fn names_snapshot(body: &str) -> bool {
+        serde_json::from_str::<Value>(body)
+            .ok()
+            .and_then(|error| error["error"]["message"].as_str().map(str::to_lowercase))
+            .is_some_and(|message| message.contains("snapshot"))
+    }
+    fn from_status(status: StatusCode, headers: &HeaderMap) -> Self {
+        match status {
             StatusCode::UNAUTHORIZED => ApiError::Unauthorized,
             StatusCode::TOO_MANY_REQUESTS => {
-                if let Ok(duration) = response.headers()[reqwest::header::RETRY_AFTER].to_str() {
-                    ApiError::RateLimited(duration.parse::<usize>().ok())
-                }
-                else {
-                    ApiError::RateLimited(None)
-                }
+                let retry_after = headers
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|duration| duration.to_str().ok())
+                    .and_then(|duration| duration.parse::<usize>().ok());
+                ApiError::RateLimited(retry_after)
             },
             status => ApiError::Other(status.as_u16())
         }
     }
 }
+/// Callback invoked before each request with its method and URL
+pub type RequestHook = Arc<dyn Fn(&Method, &str) + Send + Sync>;
+/// Callback invoked after each response with the request method and URL,
+/// the response status and the time the request took
+pub type ResponseHook = Arc<dyn Fn(&Method, &str, StatusCode, Duration) + Send + Sync>;
+
 /// Spotify API object
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Spotify {
     pub prefix: String,
     pub access_token: Option<String>,
     pub client_credentials_manager: Option<SpotifyClientCredentials>,
+    /// refreshes `access_token` for `auto_reauth`
+    #[serde(default)]
+    pub oauth_manager: Option<SpotifyOAuth>,
+    /// refresh token belonging to `access_token`
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// refresh the access token and retry once when a request answers 401
+    #[serde(default)]
+    pub auto_reauth: bool,
+    /// token info from the last `auto_reauth` refresh, replaces `access_token`
+    #[serde(skip)]
+    refreshed_token: Arc<Mutex<Option<TokenInfo>>>,
+    /// User-Agent header sent with every API request
+    #[serde(default = "default_user_agent")]
+    pub user_agent: String,
+    /// HTTP client used for API requests, the shared `CLIENT` when none
+    #[serde(skip)]
+    pub client: Option<Client>,
+    /// market used when a method's market is None
+    #[serde(default)]
+    pub default_market: Option<Country>,
+    /// use the current user's country when a method's market is None
+    #[serde(default)]
+    pub auto_market: bool,
+    /// country of the current user, looked up at most once for `auto_market`
+    #[serde(skip)]
+    user_market: Arc<Mutex<Option<Option<Country>>>>,
+    /// check genre seeds against `recommendation_genre_seeds` before
+    /// calling `recommendations`
+    #[serde(default)]
+    pub validate_genre_seeds: bool,
+    /// available genre seeds, fetched at most once for `validate_genre_seeds`
+    #[serde(skip)]
+    genre_seeds: Arc<Mutex<Option<Vec<String>>>>,
+    /// how many times a request answered with a 5xx status is retried
+    #[serde(default)]
+    pub max_retries: u32,
+    /// delay before the first retry, doubled on every further attempt
+    #[serde(default = "default_retry_base_delay")]
+    pub retry_base_delay: Duration,
+    /// sends the requests instead of `client`, see `transport`
+    #[serde(skip)]
+    transport: Option<Arc<dyn HttpTransport>>,
+    #[serde(skip)]
+    on_request: Option<RequestHook>,
+    #[serde(skip)]
+    on_response: Option<ResponseHook>,
+}
+fn default_user_agent() -> String {
+    concat!("rspotify/", env!("CARGO_PKG_VERSION")).to_owned()
+}
+fn default_retry_base_delay() -> Duration {
+    Duration::from_millis(500)
+}
+/// path of the current user's library of the given type, e.g. `me/tracks`
+fn library_path(_type: Type) -> Result<String, failure::Error> {
+    match _type {
+        Type::Track | Type::Album | Type::Show | Type::Episode => Ok(format!("me/{}s", _type.as_str())),
+        _ => Err(format_err!("{} can't be saved in the library", _type.as_str())),
+    }
+}
+/// coerce a limit into the `1..=max` range the endpoint accepts, larger
+/// limits would be rejected with a 400
+fn clamp_limit(limit: u32, max: u32) -> u32 {
+    let clamped = limit.clamp(1, max);
+    if clamped != limit {
+        warn!("limit {} is out of range, using {} instead", limit, clamped);
+    }
+    clamped
+}
+impl fmt::Debug for Spotify {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Spotify")
+            .field("prefix", &self.prefix)
+            .field("access_token", &self.access_token)
+            .field("client_credentials_manager", &self.client_credentials_manager)
+            .field("oauth_manager", &self.oauth_manager)
+            .field("refresh_token", &self.refresh_token)
+            .field("auto_reauth", &self.auto_reauth)
+            .field("refreshed_token", &self.refreshed_token)
+            .field("user_agent", &self.user_agent)
+            .field("client", &self.client)
+            .field("default_market", &self.default_market)
+            .field("auto_market", &self.auto_market)
+            .field("user_market", &self.user_market)
+            .field("validate_genre_seeds", &self.validate_genre_seeds)
+            .field("genre_seeds", &self.genre_seeds)
+            .field("max_retries", &self.max_retries)
+            .field("retry_base_delay", &self.retry_base_delay)
+            .field("transport", &self.transport.is_some())
+            .field("on_request", &self.on_request.is_some())
+            .field("on_response", &self.on_response.is_some())
+            .finish()
+    }
 }
 impl Spotify {
     //! If you want to check examples of all API endpoint, you could check the
@@ -93,10 +236,27 @@ impl Spotify {
             prefix: "https://api.spotify.com/v1/".to_owned(),
             access_token: None,
             client_credentials_manager: None,
-        }
-    }
-
-    // pub fn prefix(mut self, prefix: &str) -> Spotify {
+            oauth_manager: None,
+            refresh_token: None,
+            auto_reauth: false,
+            refreshed_token: Arc::new(Mutex::new(None)),
+            user_agent: default_user_agent(),
+            client: None,
+            default_market: None,
+            auto_market: false,
+            user_market: Arc::new(Mutex::new(None)),
+            validate_genre_seeds: false,
+            genre_seeds: Arc::new(Mutex::new(None)),
+            max_retries: 0,
+            retry_base_delay: default_retry_base_delay(),
+            transport: None,
+            on_request: None,
+            on_response: None,
+        }
+    }
+
+    /// base URL that relative endpoints are appended to, e.g. point it to
+    /// `http://localhost:1234/v1/` to test against a mock server
     pub fn prefix(mut self, prefix: &str) -> Spotify {
         self.prefix = prefix.to_owned();
         self
@@ -114,6 +274,126 @@ impl Spotify {
         self
     }
 
+    pub fn oauth_manager(mut self, oauth_manager: SpotifyOAuth) -> Spotify {
+        self.oauth_manager = Some(oauth_manager);
+        self
+    }
+
+    pub fn refresh_token(mut self, refresh_token: &str) -> Spotify {
+        self.refresh_token = Some(refresh_token.to_owned());
+        self
+    }
+
+    /// when enabled, a request answered with 401 refreshes the access token
+    /// with `oauth_manager` and `refresh_token` and is sent once more, so
+    /// long running apps keep working after the token expires. Without both
+    /// of them the 401 surfaces as `ApiError::Unauthorized` as usual
+    pub fn auto_reauth(mut self, auto_reauth: bool) -> Spotify {
+        self.auto_reauth = auto_reauth;
+        self
+    }
+
+    /// identify the app in the User-Agent header, e.g. `my-app/1.2`,
+    /// instead of the default `rspotify/<version>`. Panics if it isn't a
+    /// valid header value
+    pub fn user_agent(mut self, user_agent: &str) -> Spotify {
+        if let Err(e) = reqwest::header::HeaderValue::from_str(user_agent) {
+            panic!("invalid user agent {:?}: {}", user_agent, e);
+        }
+        self.user_agent = user_agent.to_owned();
+        self
+    }
+
+    /// use an already configured reqwest client (custom TLS, proxy,
+    /// connection limits...) instead of the shared one
+    pub fn with_client(mut self, client: Client) -> Spotify {
+        self.client = Some(client);
+        self
+    }
+
+    /// send requests through `transport` instead of reqwest, e.g. a stub
+    /// that answers canned JSON in tests
+    pub fn transport<T: HttpTransport + 'static>(mut self, transport: T) -> Spotify {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// route all API requests through the given HTTP/HTTPS proxy, e.g.
+    /// `http://my.proxy:8080`. Panics if the proxy URL is invalid
+    pub fn proxy(self, proxy_url: &str) -> Spotify {
+        let proxy = reqwest::Proxy::all(proxy_url)
+            .unwrap_or_else(|e| panic!("invalid proxy url {:?}: {}", proxy_url, e));
+        let client = Client::builder()
+            .proxy(proxy)
+            .build()
+            .unwrap_or_else(|e| panic!("failed to build client with proxy {:?}: {}", proxy_url, e));
+        self.with_client(client)
+    }
+
+    /// market used by every method called without one, e.g. for apps that
+    /// only operate in a single region. An explicit market still wins, and
+    /// this takes precedence over `auto_market`
+    pub fn market(mut self, market: Country) -> Spotify {
+        self.default_market = Some(market);
+        self
+    }
+
+    /// when enabled, methods called without a market use the country of the
+    /// current user instead. The country is fetched with `me` on first use
+    /// and cached once the lookup succeeds, so this requires a token
+    /// authorized by a user
+    pub fn auto_market(mut self, auto_market: bool) -> Spotify {
+        self.auto_market = auto_market;
+        self
+    }
+
+    /// when enabled, `recommendations` fails with
+    /// `ApiError::InvalidGenreSeed` on a genre seed that isn't in
+    /// `recommendation_genre_seeds`, which is fetched on first use and cached
+    pub fn validate_genre_seeds(mut self, validate_genre_seeds: bool) -> Spotify {
+        self.validate_genre_seeds = validate_genre_seeds;
+        self
+    }
+
+    /// retry requests that fail with a 5xx status up to `max_retries` times,
+    /// waiting with exponential backoff and random jitter in between. Only
+    /// GET, PUT and DELETE requests are retried. POST requests such as
+    /// `next_track`, `previous_track` or `user_playlist_add_tracks` could be
+    /// applied twice and are never retried, and neither are the position
+    /// based `user_playlist_recorder_tracks`,
+    /// `user_playlist_remove_specific_occurrenes_of_tracks` and
+    /// `user_playlist_remove_tracks_by_position`, which would move or remove
+    /// other tracks when the failed attempt was already applied. 429
+    /// responses are not retried either, they surface as
+    /// `ApiError::RateLimited`
+    pub fn max_retries(mut self, max_retries: u32) -> Spotify {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// delay before the first 5xx retry, 500ms by default
+    pub fn retry_base_delay(mut self, retry_base_delay: Duration) -> Spotify {
+        self.retry_base_delay = retry_base_delay;
+        self
+    }
+
+    /// call `hook` with the method and URL before each request is sent
+    pub fn on_request<F>(mut self, hook: F) -> Spotify
+        where F: Fn(&Method, &str) + Send + Sync + 'static
+    {
+        self.on_request = Some(Arc::new(hook));
+        self
+    }
+
+    /// call `hook` with the method, URL, status and elapsed time after each
+    /// response is received, e.g. to feed latency metrics
+    pub fn on_response<F>(mut self, hook: F) -> Spotify
+        where F: Fn(&Method, &str, StatusCode, Duration) + Send + Sync + 'static
+    {
+        self.on_response = Some(Arc::new(hook));
+        self
+    }
+
     pub fn build(self) -> Spotify {
         if self.access_token.is_none() && self.client_credentials_manager.is_none() {
             panic!("access_token and client_credentials_manager are none!!!");
@@ -121,7 +401,37 @@ impl Spotify {
         self
     }
 
+    /// build a Spotify object that authorizes with the given access token
+    pub fn from_token(access_token: &str) -> Spotify {
+        Spotify::default().access_token(access_token).build()
+    }
+
+    /// build a Spotify object that authorizes with the given client
+    /// credentials manager
+    pub fn from_client_credentials(client_credential_manager: SpotifyClientCredentials) -> Spotify {
+        Spotify::default()
+            .client_credentials_manager(client_credential_manager)
+            .build()
+    }
+
+    /// check the credentials before a batch of calls. With client credentials
+    /// this checks the cached token expiry, fetching a new token if needed;
+    /// with a raw access token it sends a `me` request, and any failure,
+    /// including a network error or a 5xx, fails the check
+    pub fn is_token_valid(&self) -> bool {
+        if self.access_token.is_some() {
+            return self.me().is_ok();
+        }
+        match self.client_credentials_manager {
+            Some(ref client_credentials_manager) => client_credentials_manager.is_token_valid(),
+            None => false,
+        }
+    }
+
     fn auth_headers(&self) -> String {
+        if let Some(ref token_info) = *self.refreshed_token.lock().unwrap() {
+            return "Bearer ".to_owned() + &token_info.access_token;
+        }
         let token = match self.access_token {
             Some(ref token) => token.to_owned(),
             None => {
@@ -136,67 +446,259 @@ impl Spotify {
         "Bearer ".to_owned() + &token
     }
 
-    fn internal_call(&self, method: Method, url: &str, payload: Option<&Value>) -> Result<String, failure::Error> {
-        let mut url: Cow<str> = url.into();
-        if !url.starts_with("http") {
-            url = ["https://api.spotify.com/v1/", &url].concat().into();
-        }
+    ///send request, `extra_headers` are added on top of the default
+    ///authorization and json content-type headers and replace them on conflict.
+    ///`retry` is false for requests that must not be sent twice even though
+    ///their method is idempotent
+    fn internal_call(&self,
+                     method: Method,
+                     url: &str,
+                     payload: Option<&Value>,
+                     extra_headers: Option<&HeaderMap>,
+                     retry: bool)
+                     -> Result<String, failure::Error> {
+        let url = self.endpoint_url(url);
 
         let mut headers = HeaderMap::new();
-        headers.insert(AUTHORIZATION, self.auth_headers().parse().unwrap());
+        // never hand the token to another host, e.g. through a tampered
+        // `next` URL
+        let is_api_url = self.is_api_url(&url);
+        if is_api_url {
+            headers.insert(AUTHORIZATION, self.auth_headers().parse().unwrap());
+        } else {
+            warn!("not sending the access token to {}, it isn't on the API host", url);
+        }
         headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+        if let Ok(user_agent) = self.user_agent.parse() {
+            headers.insert(USER_AGENT, user_agent);
+        }
+        if let Some(_extra_headers) = extra_headers {
+            for (name, value) in _extra_headers.iter() {
+                headers.insert(name.clone(), value.clone());
+            }
+        }
 
-        let mut response = {
-            let builder = CLIENT
-                .request(method, &url.into_owned())
-                .headers(headers);
+        let mut response = self.send(&method, &url, payload, &headers, retry)?;
+        if is_api_url && response.status == StatusCode::UNAUTHORIZED && self.reauth() {
+            headers.insert(AUTHORIZATION, self.auth_headers().parse().unwrap());
+            response = self.send(&method, &url, payload, &headers, retry)?;
+        }
 
-            // only add body if necessary
-            // spotify rejects GET requests that have a body with a 400 response
-            let builder = if let Some(json) = payload {
-                builder.json(json)
-            } else {
-                builder
-            };
+        if response.status.is_success() {
+            Ok(response.body)
+        } else {
+            Err(failure::Error::from(ApiError::from(&response)))
+        }
+    }
+    ///send the request through the transport, retrying 5xx responses of
+    ///idempotent requests as configured with `max_retries` unless `retry`
+    ///is false
+    fn send(&self,
+            method: &Method,
+            url: &str,
+            payload: Option<&Value>,
+            headers: &HeaderMap,
+            retry: bool)
+            -> Result<TransportResponse, failure::Error> {
+        let transport: &dyn HttpTransport = match self.transport {
+            Some(ref transport) => transport.as_ref(),
+            None => self.client.as_ref().unwrap_or(&CLIENT),
+        };
+        let mut attempt = 0;
+        loop {
+            if let Some(ref on_request) = self.on_request {
+                on_request(method, url);
+            }
+            let started = self.on_response.as_ref().map(|_| Instant::now());
+            let response = transport.call(method, url, payload, headers)?;
+            if let (Some(on_response), Some(started)) = (self.on_response.as_ref(), started) {
+                on_response(method, url, response.status, started.elapsed());
+            }
+            if !response.status.is_server_error()
+                || !retry
+                || !method.is_idempotent()
+                || attempt >= self.max_retries {
+                return Ok(response);
+            }
+            let delay = self.retry_delay(attempt);
+            warn!("{} {} answered {}, retrying in {:?}", method, url, response.status, delay);
+            thread::sleep(delay);
+            attempt += 1;
+        }
+    }
 
-            builder.send().unwrap()
+    ///refresh the access token for `auto_reauth`, returns whether a new
+    ///token is available
+    fn reauth(&self) -> bool {
+        if !self.auto_reauth {
+            return false;
+        }
+        let oauth_manager = match self.oauth_manager {
+            Some(ref oauth_manager) => oauth_manager,
+            None => return false,
+        };
+        let mut refreshed_token = self.refreshed_token.lock().unwrap();
+        // a refresh may hand out a new refresh token, prefer the latest one
+        let refresh_token = refreshed_token
+            .as_ref()
+            .and_then(|token_info| token_info.refresh_token.clone())
+            .or_else(|| self.refresh_token.clone());
+        let refresh_token = match refresh_token {
+            Some(refresh_token) => refresh_token,
+            None => return false,
         };
+        match oauth_manager.refresh_access_token(&refresh_token) {
+            Some(mut token_info) => {
+                debug!("refreshed access token after 401");
+                if token_info.refresh_token.is_none() {
+                    token_info.refresh_token = Some(refresh_token);
+                }
+                *refreshed_token = Some(token_info);
+                true
+            }
+            None => false,
+        }
+    }
 
-        let mut buf = String::new();
-        response
-            .read_to_string(&mut buf)
-            .expect("failed to read response");
-        if response.status().is_success() {
-            Ok(buf)
-        } else {
-            Err(failure::Error::from(ApiError::from(&response)))
+    ///whether `url` has the same scheme, host and port as `prefix`
+    fn is_api_url(&self, url: &str) -> bool {
+        match (Url::parse(url), Url::parse(&self.prefix)) {
+            (Ok(url), Ok(prefix)) => {
+                url.scheme() == prefix.scheme()
+                    && url.host_str() == prefix.host_str()
+                    && url.port_or_known_default() == prefix.port_or_known_default()
+            }
+            _ => false,
+        }
+    }
+
+    ///join a relative endpoint to `prefix` with exactly one slash and no
+    ///slash right before the query string, absolute URLs are kept as they are
+    fn endpoint_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        if url.starts_with("http") {
+            return url.into();
         }
+        let url = format!("{}/{}", self.prefix.trim_end_matches('/'), url.trim_start_matches('/'));
+        url.replacen("/?", "?", 1).into()
     }
+
+    ///exponential backoff for the given retry attempt, plus up to one base
+    ///delay of random jitter so that concurrent clients don't retry in lockstep
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let base = self.retry_base_delay;
+        let backoff = base * 2u32.saturating_pow(attempt.min(16));
+        let jitter_ms = base.as_secs() * 1000 + u64::from(base.subsec_millis());
+        backoff + Duration::from_millis(rand::thread_rng().gen_range(0, jitter_ms + 1))
+    }
+
     ///send get request
     fn get(&self, url: &str, params: &mut HashMap<String, String>) -> Result<String, failure::Error> {
+        let url_with_params = self.append_params(url, params);
+        self.internal_call(Method::GET, &url_with_params, None, None, true)
+    }
+
+    ///append query parameters to API path
+    fn append_params<K: fmt::Debug + Eq + Hash + ToString>(&self,
+                                                           url: &str,
+                                                           params: &HashMap<K, String>)
+                                                           -> String {
+        let mut url_with_params = url.to_owned();
         if !params.is_empty() {
-            let param: String = convert_map_to_string(params);
-            let mut url_with_params = url.to_owned();
-            url_with_params.push('?');
-            url_with_params.push_str(&param);
-            self.internal_call(Method::GET, &url_with_params, None)
-        } else {
-            self.internal_call(Method::GET, url, None)
+            // some endpoints already carry `?ids=...` in the path
+            if url.contains('?') {
+                url_with_params.push('&');
+            } else {
+                url_with_params.push('?');
+            }
+            url_with_params.push_str(&convert_map_to_string(params));
         }
+        url_with_params
     }
 
     ///send post request
     fn post(&self, url: &str, payload: &Value) -> Result<String, failure::Error> {
-        self.internal_call(Method::POST, url, Some(payload))
+        self.internal_call(Method::POST, url, Some(payload), None, true)
     }
     ///send put request
     fn put(&self, url: &str, payload: &Value) -> Result<String, failure::Error> {
-        self.internal_call(Method::PUT, url, Some(payload))
+        self.internal_call(Method::PUT, url, Some(payload), None, true)
+    }
+    ///send put request that is never retried, e.g. a position based reorder
+    fn put_once(&self, url: &str, payload: &Value) -> Result<String, failure::Error> {
+        self.internal_call(Method::PUT, url, Some(payload), None, false)
     }
 
     /// send delete request
     fn delete(&self, url: &str, payload: &Value) -> Result<String, failure::Error> {
-        self.internal_call(Method::DELETE, url, Some(payload))
+        self.internal_call(Method::DELETE, url, Some(payload), None, true)
+    }
+    ///send delete request that is never retried, e.g. a position based removal
+    fn delete_once(&self, url: &str, payload: &Value) -> Result<String, failure::Error> {
+        self.internal_call(Method::DELETE, url, Some(payload), None, false)
+    }
+
+    ///send get request to an endpoint that isn't wrapped yet, `endpoint` is
+    ///either relative to the API prefix, e.g. `me/shows`, or a full URL.
+    ///Returns the response as raw JSON, `Value::Null` for an empty body.
+    pub fn get_raw(&self, endpoint: &str, params: HashMap<&str, String>) -> Result<Value, failure::Error> {
+        let mut params: HashMap<String, String> = params
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect();
+        let result = self.get(endpoint, &mut params)?;
+        self.convert_raw(&result)
+    }
+
+    ///send post request to an endpoint that isn't wrapped yet, see `get_raw`
+    pub fn post_raw(&self, endpoint: &str, payload: &Value) -> Result<Value, failure::Error> {
+        let result = self.post(endpoint, payload)?;
+        self.convert_raw(&result)
+    }
+
+    ///send put request to an endpoint that isn't wrapped yet, see `get_raw`
+    pub fn put_raw(&self, endpoint: &str, payload: &Value) -> Result<Value, failure::Error> {
+        let result = self.put(endpoint, payload)?;
+        self.convert_raw(&result)
+    }
+
+    ///send delete request to an endpoint that isn't wrapped yet, see `get_raw`
+    pub fn delete_raw(&self, endpoint: &str, payload: &Value) -> Result<Value, failure::Error> {
+        let result = self.delete(endpoint, payload)?;
+        self.convert_raw(&result)
+    }
+
+    ///send a request to any endpoint and deserialize the response into a
+    ///caller-provided type, for endpoints that aren't wrapped yet
+    ///Parameters:
+    ///- method - the HTTP method
+    ///- endpoint - relative to the API prefix, e.g. `me/shows`, or a full URL
+    ///- params - query parameters
+    ///- body - optional JSON body
+    pub fn request<T: DeserializeOwned>(&self,
+                                        method: Method,
+                                        endpoint: &str,
+                                        params: HashMap<&str, String>,
+                                        body: Option<&Value>)
+                                        -> Result<T, failure::Error> {
+        let url = self.append_params(endpoint, &params);
+        let result = self.internal_call(method, &url, body, None, true)?;
+        self.convert_result::<T>(&result)
+    }
+
+    ///fetch the page a `Page::next` or `Page::previous` URL points to
+    ///Parameters:
+    ///- url - the fully-qualified URL of the page
+    pub fn get_page<T: DeserializeOwned>(&self, url: &str) -> Result<Page<T>, failure::Error> {
+        let result = self.get(url, &mut HashMap::new())?;
+        self.convert_result::<Page<T>>(&result)
+    }
+
+    fn convert_raw(&self, input: &str) -> Result<Value, failure::Error> {
+        if input.is_empty() {
+            Ok(Value::Null)
+        } else {
+            self.convert_result::<Value>(input)
+        }
     }
 
     ///[get-track](https://developer.spotify.com/web-api/get-track/)
@@ -214,23 +716,69 @@ impl Spotify {
     ///returns a list of tracks given a list of track IDs, URIs, or URLs
     ///Parameters:
     ///- track_ids - a list of spotify URIs, URLs or IDs
-    ///- market - an ISO 3166-1 alpha-2 country code.
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`
+    ///  to apply track relinking with the user's own country.
     pub fn tracks(&self, track_ids: Vec<&str>, market: Option<Country>) -> Result<FullTracks, failure::Error> {
         let mut ids: Vec<String> = vec![];
         for track_id in track_ids {
             ids.push(self.get_id(Type::Track, track_id));
         }
-        let url = format!("tracks/?ids={}", ids.join(","));
+        let url = format!("tracks?ids={}", ids.join(","));
         // url.push_str(&ids.join(","));
         let mut params: HashMap<String, String> = HashMap::new();
-        if let Some(_market) = market {
-            params.insert("market".to_owned(), _market.as_str().to_owned());
-        }
+        self.insert_market(&mut params, market);
         trace!("{:?}", &url);
         let result = self.get(&url, &mut params)?;
         self.convert_result::<FullTracks>(&result)
     }
 
+    ///[get-several-tracks](https://developer.spotify.com/web-api/get-several-tracks/)
+    ///returns any number of tracks, fetched by `tracks` in batches of 50 on
+    ///up to `concurrency` threads at once. The first failing batch, e.g. an
+    ///`ApiError::RateLimited`, stops the remaining ones and is returned
+    ///Parameters:
+    ///- track_ids - a list of spotify URIs, URLs or IDs
+    ///- concurrency - how many batches are requested at the same time,
+    ///  defaults to 4 to stay clear of the rate limit
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`
+    ///  to apply track relinking with the user's own country.
+    pub fn tracks_concurrent<C: Into<Option<usize>>>(&self,
+                                                     track_ids: Vec<String>,
+                                                     concurrency: C,
+                                                     market: Option<Country>)
+                                                     -> Result<Vec<FullTrack>, failure::Error> {
+        let market = self.market_or_default(market);
+        let chunks: Vec<&[String]> = track_ids.chunks(50).collect();
+        let workers = concurrency.into().unwrap_or(4).clamp(1, chunks.len().max(1));
+        let next_chunk = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<Result<FullTracks, failure::Error>>>> =
+            Mutex::new(chunks.iter().map(|_| None).collect());
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let index = next_chunk.fetch_add(1, Ordering::SeqCst);
+                    let chunk = match chunks.get(index) {
+                        Some(chunk) => chunk,
+                        None => break,
+                    };
+                    let result = self.tracks(chunk.iter().map(|id| id.as_str()).collect(), market);
+                    let failed = result.is_err();
+                    results.lock().unwrap()[index] = Some(result);
+                    if failed {
+                        // leave the remaining batches alone
+                        next_chunk.store(chunks.len(), Ordering::SeqCst);
+                        break;
+                    }
+                });
+            }
+        });
+        let mut tracks = Vec::with_capacity(track_ids.len());
+        for result in results.into_inner().unwrap().into_iter().flatten() {
+            tracks.extend(result?.tracks);
+        }
+        Ok(tracks)
+    }
+
     ///[get-artist](https://developer.spotify.com/web-api/get-artist/)
     ///returns a single artist given the artist's ID, URI or URL
     ///Parameters:
@@ -252,7 +800,7 @@ impl Spotify {
         for artist_id in artist_ids {
             ids.push(self.get_id(Type::Artist, &artist_id));
         }
-        let url = format!("artists/?ids={}", ids.join(","));
+        let url = format!("artists?ids={}", ids.join(","));
         // url.push_str(&ids.join(","));
         let result = self.get(&url, &mut HashMap::new())?;
         self.convert_result::<FullArtists>(&result)
@@ -263,7 +811,7 @@ impl Spotify {
     /// - artist_id - the artist ID, URI or URL
     /// - album_type - 'album', 'single', 'appears_on', 'compilation'
     /// - country - limit the response to one particular country.
-    /// - limit  - the number of albums to return
+    /// - limit  - the number of albums to return, at most 50
     /// - offset - the index of the first album to return
     pub fn artist_albums(&self,
                          artist_id: &str,
@@ -274,7 +822,7 @@ impl Spotify {
                          -> Result<Page<SimplifiedAlbum>, failure::Error> {
         let mut params: HashMap<String, String> = HashMap::new();
         if let Some(_limit) = limit {
-            params.insert("limit".to_owned(), _limit.to_string());
+            params.insert("limit".to_owned(), clamp_limit(_limit, 50).to_string());
         }
         if let Some(_album_type) = album_type {
             params.insert("album_type".to_owned(), _album_type.as_str().to_owned());
@@ -293,6 +841,60 @@ impl Spotify {
         self.convert_result::<Page<SimplifiedAlbum>>(&result)
     }
 
+    ///[get-artists-albums](https://developer.spotify.com/web-api/get-artists-albums/)
+    ///  Get all of an artist's albums, paging through `artist_albums`
+    /// - artist_id - the artist ID, URI or URL
+    /// - album_type - 'album', 'single', 'appears_on', 'compilation'
+    /// - country - limit the response to one particular country.
+    /// - dedup - without a country the same album can be listed once per
+    ///   market grouping, set this to keep only the first album with a given
+    ///   id. Defaults to false. Copies of a release published under
+    ///   different ids are kept, collapse them with
+    ///   `SimplifiedAlbum::is_same_release_as` where that's wanted
+    pub fn artist_albums_all<D: Into<Option<bool>>>(&self,
+                                                    artist_id: &str,
+                                                    album_type: Option<AlbumType>,
+                                                    country: Option<Country>,
+                                                    dedup: D)
+                                                    -> Result<Vec<SimplifiedAlbum>, failure::Error> {
+        let mut albums = vec![];
+        let mut offset = 0;
+        loop {
+            let page = self.artist_albums(artist_id, album_type, country, Some(50), Some(offset))?;
+            let fetched = page.items.len() as u32;
+            offset += fetched;
+            albums.extend(page.items);
+            if page.next.is_none() || fetched == 0 {
+                break;
+            }
+        }
+        if dedup.into().unwrap_or(false) {
+            albums = albums
+                .into_iter()
+                .unique_by(|album| album.id.clone())
+                .collect();
+        }
+        Ok(albums)
+    }
+
+    ///[get-artists-albums](https://developer.spotify.com/web-api/get-artists-albums/)
+    ///  Get an artist's whole discography, with every album type fetched
+    ///  completely by `artist_albums_all`
+    /// - artist_id - the artist ID, URI or URL
+    /// - country - limit the response to one particular country.
+    pub fn artist_discography(&self,
+                              artist_id: &str,
+                              country: Option<Country>)
+                              -> Result<Discography, failure::Error> {
+        let albums_of = |album_type| self.artist_albums_all(artist_id, Some(album_type), country, None);
+        Ok(Discography {
+            albums: albums_of(AlbumType::Album)?,
+            singles: albums_of(AlbumType::Single)?,
+            compilations: albums_of(AlbumType::Compilation)?,
+            appears_on: albums_of(AlbumType::AppearsOn)?,
+        })
+    }
+
     ///[get artists to tracks](https://developer.spotify.com/web-api/get-artists-top-tracks/)
     /// Get Spotify catalog information about an artist's top 10 tracks by country.
     ///    Parameters:
@@ -311,22 +913,25 @@ impl Spotify {
         params.insert("country".to_owned(), country);
         let trid = self.get_id(Type::Artist, artist_id);
         let url = format!("artists/{}/top-tracks", trid);
-
         let result = self.get(&url, &mut params)?;
         self.convert_result::<FullTracks>(&result)
-        // match self.get(&mut url, &mut params) {
-        //     Ok(result) => {
-        //         // let mut albums: Albums = ;
-        //         match serde_json::from_str::<FullTracks>(&result) {
-        //             Ok(_tracks) => Some(_tracks),
-        //             Err(why) => {
-        //                 eprintln!("convert albums from String to Albums failed {:?}", why);
-        //                 None
-        //             }
-        //         }
-        //     }
-        //     Err(_) => None,
-        // }
+    }
+
+    ///[get artists to tracks](https://developer.spotify.com/web-api/get-artists-top-tracks/)
+    /// Get an artist's top tracks like `artist_top_tracks`, keeping only the
+    /// first `limit` of them. The API itself always answers with up to 10
+    ///    Parameters:
+    ///        - artist_id - the artist ID, URI or URL
+    ///        - country - limit the response to one particular country.
+    ///        - limit - the number of tracks to keep
+    pub fn artist_top_tracks_limited<T: Into<Option<Country>>>(&self,
+                                                               artist_id: &str,
+                                                               country: T,
+                                                               limit: u32)
+                                                               -> Result<FullTracks, failure::Error> {
+        let mut tracks = self.artist_top_tracks(artist_id, country)?;
+        tracks.tracks.truncate(limit as usize);
+        Ok(tracks)
     }
 
     ///[get related artists](https://developer.spotify.com/web-api/get-related-artists/)
@@ -360,12 +965,16 @@ impl Spotify {
     ///returns a list of albums given the album IDs, URIs, or URLs
     ///Parameters:
     ///- albums_ids - a list of  album IDs, URIs or URLs
+    ///
+    ///Duplicated albums are only requested once, so the returned albums
+    ///follow the order in which each album first appears in `album_ids`.
     pub fn albums(&self, album_ids: Vec<String>) -> Result<FullAlbums, failure::Error> {
-        let mut ids: Vec<String> = vec![];
-        for album_id in album_ids {
-            ids.push(self.get_id(Type::Album, &album_id));
-        }
-        let url = format!("albums/?ids={}", ids.join(","));
+        let ids: Vec<String> = album_ids
+            .iter()
+            .map(|album_id| self.get_id(Type::Album, album_id))
+            .unique()
+            .collect();
+        let url = format!("albums?ids={}", ids.join(","));
         // url.push_str(&ids.join(","));
         let result = self.get(&url, &mut HashMap::new())?;
         self.convert_result::<FullAlbums>(&result)
@@ -376,8 +985,8 @@ impl Spotify {
     ///Get Spotify catalog information about artists, albums, tracks or
     /// playlists that match a keyword string.
     ///            Parameters:
-    ///- q - the search query
-    ///- limit  - the number of items to return
+    ///- q - the search query, see `SearchQuery` to build one with filters
+    ///- limit  - the number of items to return, at most 50
     ///- offset - the index of the first item to return
     ///- type - the type of item to return. One of 'artist', 'album',
     ///'track' or 'playlist'
@@ -390,11 +999,9 @@ impl Spotify {
                                                           market: Option<Country>)
                                                           -> Result<String, failure::Error> {
         let mut params = HashMap::new();
-        let limit = limit.into().unwrap_or(10);
+        let limit = clamp_limit(limit.into().unwrap_or(10), 50);
         let offset = offset.into().unwrap_or(0);
-        if let Some(_market) = market {
-            params.insert("market".to_owned(), _market.as_str().to_owned());
-        }
+        self.insert_market(&mut params, market);
         params.insert("limit".to_owned(), limit.to_string());
         params.insert("offset".to_owned(), offset.to_string());
         params.insert("q".to_owned(), q.to_owned());
@@ -408,8 +1015,8 @@ impl Spotify {
     ///Get Spotify catalog information about artists, albums, tracks or
     /// playlists that match a keyword string.
     ///            Parameters:
-    ///- q - the search query
-    ///- limit  - the number of items to return
+    ///- q - the search query, see `SearchQuery` to build one with filters
+    ///- limit  - the number of items to return, at most 50
     ///- offset - the index of the first item to return
     ///'track' or 'playlist'
     ///- market - An ISO 3166-1 alpha-2 country code or the string from_token.
@@ -428,19 +1035,31 @@ impl Spotify {
     ///Get Spotify catalog information about artists, albums, tracks or
     /// playlists that match a keyword string.
     ///            Parameters:
-    ///- q - the search query
-    ///- limit  - the number of items to return
+    ///- q - the search query, see `SearchQuery` to build one with filters
+    ///- limit  - the number of items to return, at most 50
     ///- offset - the index of the first item to return
     ///'track' or 'playlist'
     ///- market - An ISO 3166-1 alpha-2 country code or the string from_token.
+    ///- genres - only keep artists with at least one of these genres
+    ///  (case-insensitive). The API can't filter artists by genre, so this is
+    ///  applied to the returned page and `total` still counts every match
     pub fn search_artist<L: Into<Option<u32>>, O: Into<Option<u32>>>(&self,
                                                                      q: &str,
                                                                      limit: L,
                                                                      offset: O,
-                                                                     market: Option<Country>)
+                                                                     market: Option<Country>,
+                                                                     genres: Option<Vec<String>>)
                                                                      -> Result<SearchArtists, failure::Error> {
         let result = self.search(q, SearchType::Artist, limit, offset, market)?;
-        self.convert_result::<SearchArtists>(&result)
+        let mut search_artists = self.convert_result::<SearchArtists>(&result)?;
+        if let Some(_genres) = genres {
+            search_artists.artists.items.retain(|artist| {
+                artist.genres.iter().any(|genre| {
+                    _genres.iter().any(|wanted| genre.eq_ignore_ascii_case(wanted))
+                })
+            });
+        }
+        Ok(search_artists)
     }
 
     ///search item, type is track
@@ -448,8 +1067,8 @@ impl Spotify {
     ///Get Spotify catalog information about artists, albums, tracks or
     /// playlists that match a keyword string.
     ///            Parameters:
-    ///- q - the search query
-    ///- limit  - the number of items to return
+    ///- q - the search query, see `SearchQuery` to build one with filters
+    ///- limit  - the number of items to return, at most 50
     ///- offset - the index of the first item to return
     ///'track' or 'playlist'
     ///- market - An ISO 3166-1 alpha-2 country code or the string from_token.
@@ -467,8 +1086,8 @@ impl Spotify {
     ///Get Spotify catalog information about artists, albums, tracks or
     /// playlists that match a keyword string.
     ///            Parameters:
-    ///- q - the search query
-    ///- limit  - the number of items to return
+    ///- q - the search query, see `SearchQuery` to build one with filters
+    ///- limit  - the number of items to return, at most 50
     ///- offset - the index of the first item to return
     ///'track' or 'playlist'
     ///- market - An ISO 3166-1 alpha-2 country code or the string from_token.
@@ -487,25 +1106,120 @@ impl Spotify {
     ///Get Spotify catalog information about an album's tracks
     ///Parameters:
     ///- album_id - the album ID, URI or URL
-    ///- limit  - the number of items to return
+    ///- limit  - the number of items to return, at most 50
     ///- offset - the index of the first item to return
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`
+    ///  to apply track relinking with the user's own country.
     pub fn album_track<L: Into<Option<u32>>, O: Into<Option<u32>>>
         (&self,
          album_id: &str,
          limit: L,
-         offset: O)
+         offset: O,
+         market: Option<Country>)
          -> Result<Page<SimplifiedTrack>, failure::Error> {
         let mut params = HashMap::new();
         let trid = self.get_id(Type::Album, album_id);
         let url = format!("albums/{}/tracks", trid);
         // url.push_str(&trid);
         // url.push_str("/tracks");
-        params.insert("limit".to_owned(), limit.into().unwrap_or(50).to_string());
+        params.insert("limit".to_owned(), clamp_limit(limit.into().unwrap_or(50), 50).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
+        self.insert_market(&mut params, market);
         let result = self.get(&url, &mut params)?;
         self.convert_result::<Page<SimplifiedTrack>>(&result)
     }
 
+    ///[get albums tracks](https://developer.spotify.com/web-api/get-albums-tracks/)
+    ///Get all the tracks of an album, following `next` until the last page,
+    ///for compilations and box sets with more than 50 tracks
+    ///Parameters:
+    ///- album_id - the album ID, URI or URL
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`
+    ///  to apply track relinking with the user's own country.
+    pub fn album_tracks_all(&self,
+                            album_id: &str,
+                            market: Option<Country>)
+                            -> Result<Vec<SimplifiedTrack>, failure::Error> {
+        let mut page = self.album_track(album_id, 50, 0, market)?;
+        let mut tracks = vec![];
+        loop {
+            tracks.extend(page.items);
+            match page.next {
+                Some(next) => page = self.get_page::<SimplifiedTrack>(&next)?,
+                None => break,
+            }
+        }
+        Ok(tracks)
+    }
+
+    ///[get a show](https://developer.spotify.com/documentation/web-api/reference/shows/get-a-show/)
+    ///Get Spotify catalog information for a single show
+    ///Parameters:
+    ///- id - the show ID, URI or URL
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`.
+    ///  Shows are only returned for a market, either this one or the user's
+    pub fn show(&self, id: &str, market: Option<Country>) -> Result<FullShow, failure::Error> {
+        let mut params = HashMap::new();
+        self.insert_market(&mut params, market);
+        let trid = self.get_id(Type::Show, id);
+        let url = format!("shows/{}", trid);
+        let result = self.get(&url, &mut params)?;
+        self.convert_result::<FullShow>(&result)
+    }
+
+    ///[get a show's episodes](https://developer.spotify.com/documentation/web-api/reference/shows/get-shows-episodes/)
+    ///Get Spotify catalog information about a show's episodes
+    ///Parameters:
+    ///- id - the show ID, URI or URL
+    ///- limit  - the number of items to return, at most 50
+    ///- offset - the index of the first item to return
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`
+    pub fn show_episodes<L: Into<Option<u32>>, O: Into<Option<u32>>>
+        (&self,
+         id: &str,
+         limit: L,
+         offset: O,
+         market: Option<Country>)
+         -> Result<Page<SimplifiedEpisode>, failure::Error> {
+        let mut params = HashMap::new();
+        params.insert("limit".to_owned(), clamp_limit(limit.into().unwrap_or(20), 50).to_string());
+        params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
+        self.insert_market(&mut params, market);
+        let trid = self.get_id(Type::Show, id);
+        let url = format!("shows/{}/episodes", trid);
+        let result = self.get(&url, &mut params)?;
+        self.convert_result::<Page<SimplifiedEpisode>>(&result)
+    }
+
+    ///[get an episode](https://developer.spotify.com/documentation/web-api/reference/episodes/get-an-episode/)
+    ///Get Spotify catalog information for a single episode
+    ///Parameters:
+    ///- id - the episode ID, URI or URL
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`.
+    ///  Episodes are only returned for a market, either this one or the user's
+    pub fn episode(&self, id: &str, market: Option<Country>) -> Result<FullEpisode, failure::Error> {
+        let mut params = HashMap::new();
+        self.insert_market(&mut params, market);
+        let trid = self.get_id(Type::Episode, id);
+        let url = format!("episodes/{}", trid);
+        let result = self.get(&url, &mut params)?;
+        self.convert_result::<FullEpisode>(&result)
+    }
+
+    ///[get several episodes](https://developer.spotify.com/documentation/web-api/reference/episodes/get-several-episodes/)
+    ///Get Spotify catalog information for multiple episodes
+    ///Parameters:
+    ///- ids - a list of episode IDs, URIs or URLs, at most 50
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`
+    pub fn episodes(&self, ids: Vec<String>, market: Option<Country>) -> Result<Vec<FullEpisode>, failure::Error> {
+        let mut params = HashMap::new();
+        self.insert_market(&mut params, market);
+        let ids: Vec<String> = ids.iter().map(|id| self.get_id(Type::Episode, id)).collect();
+        let url = format!("episodes?ids={}", ids.join(","));
+        let result = self.get(&url, &mut params)?;
+        self.convert_result::<FullEpisodes>(&result).map(|episodes| episodes.episodes)
+    }
+
     ///[get users profile](https://developer.spotify.com/web-api/get-users-profile/)
     ///Gets basic profile information about a Spotify User
     ///Parameters:
@@ -529,9 +1243,7 @@ impl Spotify {
         if let Some(_fields) = fields {
             params.insert("fields".to_owned(), _fields.to_string());
         }
-        if let Some(_market) = market {
-            params.insert("market".to_owned(), _market.as_str().to_owned());
-        }
+        self.insert_market(&mut params, market);
 
         let plid = self.get_id(Type::Playlist, playlist_id);
         let url = format!("playlists/{}", plid);
@@ -539,10 +1251,30 @@ impl Spotify {
         self.convert_result::<FullPlaylist>(&result)
     }
 
+    ///[get playlist](https://developer.spotify.com/documentation/web-api/reference/playlists/get-playlist/)
+    ///Get several playlists one after another, the API has no batch endpoint.
+    ///The results are aligned with `playlist_ids`, a playlist that failed
+    ///doesn't stop the others and keeps its own error
+    ///Parameters:
+    ///- playlist_ids - a list of playlist IDs, URIs or URLs
+    ///- fields - which fields to return, see `FieldsBuilder` to build them
+    ///- market - an ISO 3166-1 alpha-2 country code.
+    pub fn playlists(&self,
+                     playlist_ids: &[String],
+                     fields: Option<&str>,
+                     market: Option<Country>)
+                     -> Vec<Result<FullPlaylist, failure::Error>> {
+        let market = self.market_or_default(market);
+        playlist_ids
+            .iter()
+            .map(|playlist_id| self.playlist(playlist_id, fields, market))
+            .collect()
+    }
+
     ///[get users playlists](https://developer.spotify.com/web-api/get-a-list-of-current-users-playlists/)
     ///Get current user playlists without required getting his profile
     ///Parameters:
-    ///- limit  - the number of items to return
+    ///- limit  - the number of items to return, at most 50
     ///- offset - the index of the first item to return
     pub fn current_user_playlists<L: Into<Option<u32>>, O: Into<Option<u32>>>
         (&self,
@@ -550,7 +1282,7 @@ impl Spotify {
          offset: O)
          -> Result<Page<SimplifiedPlaylist>, failure::Error> {
         let mut params = HashMap::new();
-        params.insert("limit".to_owned(), limit.into().unwrap_or(50).to_string());
+        params.insert("limit".to_owned(), clamp_limit(limit.into().unwrap_or(50), 50).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
 
         let url = String::from("me/playlists");
@@ -558,11 +1290,28 @@ impl Spotify {
         self.convert_result::<Page<SimplifiedPlaylist>>(&result)
     }
 
+    ///Get all playlists of the current user, paging through
+    ///`current_user_playlists` 50 playlists at a time
+    pub fn current_user_all_playlists(&self) -> Result<Vec<SimplifiedPlaylist>, failure::Error> {
+        let mut playlists = vec![];
+        let mut offset = 0;
+        loop {
+            let page = self.current_user_playlists(50, offset)?;
+            let fetched = page.items.len() as u32;
+            offset += fetched;
+            playlists.extend(page.items);
+            if page.next.is_none() || fetched == 0 {
+                break;
+            }
+        }
+        Ok(playlists)
+    }
+
     ///[get list users playlists](https://developer.spotify.com/web-api/get-list-users-playlists/)
     ///Gets playlists of a user
     ///Parameters:
     ///- user_id - the id of the usr
-    ///- limit  - the number of items to return
+    ///- limit  - the number of items to return, at most 50
     ///- offset - the index of the first item to return
     pub fn user_playlists<L: Into<Option<u32>>, O: Into<Option<u32>>>
         (&self,
@@ -571,7 +1320,7 @@ impl Spotify {
          offset: O)
          -> Result<Page<SimplifiedPlaylist>, failure::Error> {
         let mut params = HashMap::new();
-        params.insert("limit".to_owned(), limit.into().unwrap_or(50).to_string());
+        params.insert("limit".to_owned(), clamp_limit(limit.into().unwrap_or(50), 50).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
         let url = format!("users/{}/playlists", user_id);
         let result = self.get(&url, &mut params)?;
@@ -583,7 +1332,7 @@ impl Spotify {
     ///Parameters:
     ///- user_id - the id of the user
     ///- playlist_id - the id of the playlist
-    ///- fields - which fields to return
+    ///- fields - which fields to return, see `FieldsBuilder` to build them
     pub fn user_playlist(&self,
                          user_id: &str,
                          playlist_id: Option<&mut str>,
@@ -594,9 +1343,7 @@ impl Spotify {
         if let Some(_fields) = fields {
             params.insert("fields".to_owned(), _fields.to_string());
         }
-        if let Some(_market) = market {
-            params.insert("market".to_owned(), _market.as_str().to_owned());
-        }
+        self.insert_market(&mut params, market);
         match playlist_id {
             Some(_playlist_id) => {
                 let plid = self.get_id(Type::Playlist, _playlist_id);
@@ -612,15 +1359,55 @@ impl Spotify {
         }
     }
 
+    ///[get playlist](https://developer.spotify.com/documentation/web-api/reference/playlists/get-playlist/)
+    ///Get only the current snapshot_id of a playlist, a cheap way to poll
+    ///whether it changed without downloading its tracks
+    ///Parameters:
+    ///- user_id - the id of the user
+    ///- playlist_id - the id of the playlist
+    pub fn playlist_snapshot_id(&self, user_id: &str, playlist_id: &str) -> Result<String, failure::Error> {
+        let mut params = HashMap::new();
+        params.insert("fields".to_owned(), "snapshot_id".to_owned());
+        let plid = self.get_id(Type::Playlist, playlist_id);
+        let url = format!("users/{}/playlists/{}", user_id, plid);
+        let result = self.get(&url, &mut params)?;
+        self.convert_result::<CUDResult>(&result).map(|result| result.snapshot_id)
+    }
+
+    ///[get playlist](https://developer.spotify.com/documentation/web-api/reference/playlists/get-playlist/)
+    ///Get a playlist only if it changed since `known_snapshot`, comparing
+    ///snapshot ids with `playlist_snapshot_id` first. Returns None when it
+    ///is unchanged, so polling apps skip downloading the same tracks again
+    ///Parameters:
+    ///- user_id - the id of the user
+    ///- playlist_id - the id of the playlist
+    ///- known_snapshot - the snapshot_id of the copy the caller already has
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`
+    pub fn user_playlist_if_changed(&self,
+                                    user_id: &str,
+                                    playlist_id: &str,
+                                    known_snapshot: &str,
+                                    market: Option<Country>)
+                                    -> Result<Option<FullPlaylist>, failure::Error> {
+        if self.playlist_snapshot_id(user_id, playlist_id)? == known_snapshot {
+            return Ok(None);
+        }
+        let mut playlist_id = playlist_id.to_owned();
+        self.user_playlist(user_id, Some(&mut playlist_id), None, market).map(Some)
+    }
+
     ///[get playlists tracks](https://developer.spotify.com/web-api/get-playlists-tracks/)
     ///Get full details of the tracks of a playlist owned by a user
     ///Parameters:
     ///- user_id - the id of the user
     ///- playlist_id - the id of the playlist
-    ///- fields - which fields to return
-    ///- limit - the maximum number of tracks to return
+    ///- fields - which fields to return, see `FieldsBuilder` to build them
+    ///- limit - the maximum number of tracks to return, defaults to the
+    ///  endpoint maximum of 100
     ///- offset - the index of the first track to return
-    ///- market - an ISO 3166-1 alpha-2 country code.
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`
+    ///  to apply track relinking with the user's own country. When None and
+    ///  `auto_market` is set, the authenticated user's country is used.
     pub fn user_playlist_tracks<L: Into<Option<u32>>, O: Into<Option<u32>>>
         (&self,
          user_id: &str,
@@ -631,11 +1418,9 @@ impl Spotify {
          market: Option<Country>)
          -> Result<Page<PlaylistTrack>, failure::Error> {
         let mut params = HashMap::new();
-        params.insert("limit".to_owned(), limit.into().unwrap_or(50).to_string());
+        params.insert("limit".to_owned(), clamp_limit(limit.into().unwrap_or(100), 100).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
-        if let Some(_market) = market {
-            params.insert("market".to_owned(), _market.as_str().to_owned());
-        }
+        self.insert_market(&mut params, market);
         if let Some(_fields) = fields {
             params.insert("fields".to_owned(), _fields.to_string());
         }
@@ -653,18 +1438,30 @@ impl Spotify {
     ///- name - the name of the playlist
     ///- public - is the created playlist public
     ///- description - the description of the playlist
-    pub fn user_playlist_create<P: Into<Option<bool>>, D: Into<Option<String>>>
+    ///- collaborative - is the created playlist collaborative, a
+    ///  collaborative playlist can't be public so `public` defaults to false
+    ///  and setting both to true is an error
+    pub fn user_playlist_create<P: Into<Option<bool>>,
+                                D: Into<Option<String>>,
+                                C: Into<Option<bool>>>
         (&self,
          user_id: &str,
          name: &str,
          public: P,
-         description: D)
+         description: D,
+         collaborative: C)
          -> Result<FullPlaylist, failure::Error> {
-        let public = public.into().unwrap_or(true);
+        let public = public.into();
+        let collaborative = collaborative.into().unwrap_or(false);
+        if collaborative && public == Some(true) {
+            return Err(format_err!("a collaborative playlist can't be public, set public to false"));
+        }
+        let public = public.unwrap_or(!collaborative);
         let description = description.into().unwrap_or_else(|| "".to_owned());
         let params = json!({
             "name": name,
             "public": public,
+            "collaborative": collaborative,
             "description": description
         });
         let url = format!("users/{}/playlists", user_id);
@@ -721,28 +1518,43 @@ impl Spotify {
     ///Parameters:
     ///- user_id - the id of the user
     ///- playlist_id - the id of the playlist
-    ///- track_ids - a list of track URIs, URLs or IDs
-    ///- position - the position to add the tracks
+    ///- track_ids - a list of track URIs, URLs or IDs, any number of them.
+    ///  The API takes at most 100 per request, so more are added in batches
+    ///  of 100 and the snapshot_id of the last batch is returned. When a
+    ///  batch fails, the batches before it stay added
+    ///- position - the zero-based index to insert the tracks at; if omitted
+    ///  the tracks are appended to the end of the playlist
     pub fn user_playlist_add_tracks(&self,
                                     user_id: &str,
                                     playlist_id: &str,
                                     track_ids: &[String],
-                                    position: Option<i32>)
+                                    position: Option<u32>)
                                     -> Result<CUDResult, failure::Error> {
         let plid = self.get_id(Type::Playlist, playlist_id);
         let uris: Vec<String> = track_ids
             .iter()
             .map(|id| self.get_uri(Type::Track, id))
             .collect();
-        let mut params = Map::new();
-        if let Some(_position) = position {
-            params.insert("position".to_owned(), _position.into());
-        }
-        params.insert("uris".to_owned(), uris.into());
         let url = format!("users/{}/playlists/{}/tracks",user_id,plid);
-        let result = self.post(&url, &Value::Object(params))?;
-        self.convert_result::<CUDResult>(&result)
-
+        let batches: Vec<&[String]> = if uris.is_empty() {
+            vec![&uris]
+        } else {
+            uris.chunks(100).collect()
+        };
+        let mut cud_result = None;
+        let mut added = 0;
+        for batch in batches {
+            let mut params = Map::new();
+            if let Some(_position) = position {
+                // later batches go right after the ones already inserted
+                params.insert("position".to_owned(), (_position + added).into());
+            }
+            params.insert("uris".to_owned(), batch.into());
+            let result = self.post(&url, &Value::Object(params))?;
+            cud_result = Some(self.convert_result::<CUDResult>(&result)?);
+            added += batch.len() as u32;
+        }
+        Ok(cud_result.expect("at least one batch is sent"))
     }
     ///[replaced playlists tracks](https://developer.spotify.com/web-api/replace-playlists-tracks/)
     ///Replace all tracks in a playlist
@@ -750,12 +1562,13 @@ impl Spotify {
     ///- user - the id of the user
     ///- playlist_id - the id of the playlist
     ///- tracks - the list of track ids to add to the playlist
-
+    ///
+    ///Returns the new snapshot_id of the playlist, see `CUDResult`
     pub fn user_playlist_replace_tracks(&self,
                                         user_id: &str,
                                         playlist_id: &str,
                                         track_ids: &[String])
-                                        -> Result<(), failure::Error> {
+                                        -> Result<CUDResult, failure::Error> {
         let plid = self.get_id(Type::Playlist, playlist_id);
         let uris: Vec<String> = track_ids
             .iter()
@@ -767,10 +1580,8 @@ impl Spotify {
             "uris": uris
         });
         let url = format!("users/{}/playlists/{}/tracks",user_id,plid);
-        match self.put(&url, &params) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e),
-        }
+        let result = self.put(&url, &params)?;
+        self.convert_result::<CUDResult>(&result)
     }
 
     ///[reorder playlists tracks](https://developer.spotify.com/web-api/reorder-playlists-tracks/)
@@ -800,7 +1611,7 @@ impl Spotify {
         params.insert("range_length".to_owned(), range_length.into());
         params.insert("insert_before".to_owned(), insert_before.into());
         let url = format!("users/{}/playlists/{}/tracks",user_id,plid);
-        let result = self.put(&url, &Value::Object(params))?;
+        let result = self.put_once(&url, &Value::Object(params))?;
         self.convert_result::<CUDResult>(&result)
     }
 
@@ -863,6 +1674,9 @@ impl Spotify {
                 let uri = self.get_uri(Type::Track, &_uri.as_str().unwrap().to_owned());
                 map.insert("uri".to_owned(), uri.into());
             }
+            if let Some(_positions) = track.get("positions") {
+                map.insert("positions".to_owned(), _positions.to_owned());
+            }
             if let Some(_position) = track.get("position") {
                 map.insert("position".to_owned(), _position.to_owned());
             }
@@ -873,7 +1687,31 @@ impl Spotify {
             params.insert("snapshot_id".to_owned(), _snapshot_id.into());
         }
         let url = format!("users/{}/playlists/{}/tracks",user_id,plid);
-        let result = self.delete(&url, &Value::Object(params))?;
+        let result = self.delete_once(&url, &Value::Object(params))?;
+        self.convert_result::<CUDResult>(&result)
+    }
+
+    ///[remove tracks playlist](https://developer.spotify.com/web-api/remove-tracks-playlist/)
+    ///Removes the tracks at the given positions, as they were in the given snapshot
+    ///Parameters:
+    ///- user_id - the id of the user
+    ///- playlist_id - the id of the playlist
+    ///- positions - zero-based positions of the tracks to remove
+    ///- snapshot_id - id of the playlist snapshot the positions refer to,
+    ///  a stale snapshot fails with `ApiError::SnapshotConflict`
+    pub fn user_playlist_remove_tracks_by_position(&self,
+                                                   user_id: &str,
+                                                   playlist_id: &str,
+                                                   positions: &[u32],
+                                                   snapshot_id: &str)
+                                                   -> Result<CUDResult, failure::Error> {
+        let plid = self.get_id(Type::Playlist, playlist_id);
+        let params = json!({
+            "positions": positions,
+            "snapshot_id": snapshot_id,
+        });
+        let url = format!("users/{}/playlists/{}/tracks", user_id, plid);
+        let result = self.delete_once(&url, &params)?;
         self.convert_result::<CUDResult>(&result)
     }
 
@@ -926,7 +1764,7 @@ impl Spotify {
     ///An alias for the 'current_user' method.
     pub fn me(&self) -> Result<PrivateUser, failure::Error> {
         let mut dumb: HashMap<String, String> = HashMap::new();
-        let url = String::from("me/");
+        let url = String::from("me");
         let result = self.get(&url, &mut dumb)?;
         self.convert_result::<PrivateUser>(&result)
     }
@@ -938,26 +1776,27 @@ impl Spotify {
 
     /// [get the users currently playing track](https://developer.spotify.com/web-api/get-the-users-currently-playing-track/)
     /// Get information about the current users currently playing track.
-    pub fn current_user_playing_track(&self) -> Result<Option<Playing>, failure::Error> {
-        let mut dumb = HashMap::new();
+    /// Returns `Ok(None)` when nothing is playing on any device, while
+    /// failing to reach Spotify is an `Err`.
+    ///Parameters:
+    ///- additional_types - item types besides tracks to return, defaults to
+    ///  tracks and episodes. Episodes are left out unless requested here.
+    pub fn current_user_playing_track(&self,
+                                      additional_types: Option<Vec<Type>>)
+                                      -> Result<Option<Playing>, failure::Error> {
+        let mut params = HashMap::new();
+        params.insert("additional_types".to_owned(), self.additional_types(additional_types));
         let url = String::from("me/player/currently-playing");
-        match self.get(&url, &mut dumb) {
-            Ok(result) => {
-                if result.is_empty() {
-                    Ok(None)
-                } else {
-                    self.convert_result::<Option<Playing>>(&result)
-                }
-            }
-            Err(e) => Err(e),
-        }
+        // 204 No Content when no device is active converts to None
+        let result = self.get(&url, &mut params)?;
+        self.convert_result::<Option<Playing>>(&result)
     }
 
     ///[get user saved albums](https://developer.spotify.com/web-api/get-users-saved-albums/)
     ///Gets a list of the albums saved in the current authorized user's
     ///"Your Music" library
     ///Parameters:
-    ///- limit - the number of albums to return
+    ///- limit - the number of albums to return, at most 50
     ///- offset - the index of the first album to return
     ///- market - Provide this parameter if you want to apply Track Relinking.
     pub fn current_user_saved_albums<L: Into<Option<u32>>, O: Into<Option<u32>>>
@@ -965,7 +1804,7 @@ impl Spotify {
          limit: L,
          offset: O)
          -> Result<Page<SavedAlbum>, failure::Error> {
-        let limit = limit.into().unwrap_or(20);
+        let limit = clamp_limit(limit.into().unwrap_or(20), 50);
         let offset = offset.into().unwrap_or(0);
         let mut params = HashMap::new();
         params.insert("limit".to_owned(), limit.to_string());
@@ -976,7 +1815,7 @@ impl Spotify {
     }
     ///[get users saved tracks](https://developer.spotify.com/web-api/get-users-saved-tracks/)
     ///Parameters:
-    ///- limit - the number of tracks to return
+    ///- limit - the number of tracks to return, at most 50
     ///- offset - the index of the first track to return
     ///- market - Provide this parameter if you want to apply Track Relinking.
     pub fn current_user_saved_tracks<L: Into<Option<u32>>, O: Into<Option<u32>>>
@@ -984,7 +1823,7 @@ impl Spotify {
          limit: L,
          offset: O)
          -> Result<Page<SavedTrack>, failure::Error> {
-        let limit = limit.into().unwrap_or(20);
+        let limit = clamp_limit(limit.into().unwrap_or(20), 50);
         let offset = offset.into().unwrap_or(0);
         let mut params = HashMap::new();
         params.insert("limit".to_owned(), limit.to_string());
@@ -997,13 +1836,13 @@ impl Spotify {
     ///[get followed artists](https://developer.spotify.com/web-api/get-followed-artists/)
     ///Gets a list of the artists followed by the current authorized user
     ///Parameters:
-    ///- limit - the number of tracks to return
+    ///- limit - the number of tracks to return, at most 50
     ///- after - ghe last artist ID retrieved from the previous request
     pub fn current_user_followed_artists<L: Into<Option<u32>>>(&self,
                                                                limit: L,
                                                                after: Option<String>)
                                                                -> Result<CursorPageFullArtists, failure::Error> {
-        let limit = limit.into().unwrap_or(20);
+        let limit = clamp_limit(limit.into().unwrap_or(20), 50);
         let mut params = HashMap::new();
         params.insert("limit".to_owned(), limit.to_string());
         if let Some(_after) = after {
@@ -1015,37 +1854,82 @@ impl Spotify {
         self.convert_result::<CursorPageFullArtists>(&result)
     }
 
+    ///[save to the library](https://developer.spotify.com/documentation/web-api/reference/library/)
+    ///Save one or more items to the current user's library, the typed
+    ///`current_user_saved_*_add` and `save_shows` methods call this
+    ///Parameters:
+    ///- _type - `Type::Track`, `Type::Album`, `Type::Show` or `Type::Episode`
+    ///- ids - a list of URIs, URLs or IDs of that type
+    pub fn library_add(&self, _type: Type, ids: &[String]) -> Result<(), failure::Error> {
+        self.library_call(Method::PUT, _type, ids, HashMap::new())
+    }
+
+    ///[remove from the library](https://developer.spotify.com/documentation/web-api/reference/library/)
+    ///Remove one or more items from the current user's library
+    ///Parameters:
+    ///- _type - `Type::Track`, `Type::Album`, `Type::Show` or `Type::Episode`
+    ///- ids - a list of URIs, URLs or IDs of that type
+    pub fn library_remove(&self, _type: Type, ids: &[String]) -> Result<(), failure::Error> {
+        self.library_call(Method::DELETE, _type, ids, HashMap::new())
+    }
+
+    ///[check the library](https://developer.spotify.com/documentation/web-api/reference/library/)
+    ///Check if one or more items are saved in the current user's library
+    ///Parameters:
+    ///- _type - `Type::Track`, `Type::Album`, `Type::Show` or `Type::Episode`
+    ///- ids - a list of URIs, URLs or IDs of that type, any number of them,
+    ///  they are checked in batches of 50 and the results stay in order
+    pub fn library_contains(&self, _type: Type, ids: &[String]) -> Result<Vec<bool>, failure::Error> {
+        let path = library_path(_type)?;
+        let ids: Vec<String> = ids.iter().map(|id| self.get_id(_type, id)).collect();
+        self.contains_in_chunks(&ids, |ids| format!("{}/contains?ids={}", path, ids))
+    }
+
+    ///save to or remove from the library of `_type` with PUT or DELETE,
+    ///`params` are further query parameters such as the market
+    fn library_call(&self,
+                    method: Method,
+                    _type: Type,
+                    ids: &[String],
+                    mut params: HashMap<String, String>)
+                    -> Result<(), failure::Error> {
+        let path = library_path(_type)?;
+        let ids: Vec<String> = ids.iter().map(|id| self.get_id(_type, id)).collect();
+        params.insert("ids".to_owned(), ids.join(","));
+        let url = self.append_params(&path, &params);
+        self.internal_call(method, &url, Some(&json!({})), None, true)?;
+        Ok(())
+    }
+
     ///[remove tracks users](https://developer.spotify.com/web-api/remove-tracks-user/)
     ///Remove one or more tracks from the current user's
     ///"Your Music" library.
     ///Parameters:
     ///- track_ids - a list of track URIs, URLs or IDs
     pub fn current_user_saved_tracks_delete(&self, track_ids: &[String]) -> Result<(), failure::Error> {
-        let uris: Vec<String> = track_ids
-            .iter()
-            .map(|id| self.get_id(Type::Track, id))
-            .collect();
-        let url = format!("me/tracks/?ids={}",uris.join(","));
-        match self.delete(&url, &json!({})) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e),
-        }
+        self.library_remove(Type::Track, track_ids)
     }
 
     ///[check users saved tracks](https://developer.spotify.com/web-api/check-users-saved-tracks/)
     ///Check if one or more tracks is already saved in
     ///the current Spotify user’s “Your Music” library.
     ///Parameters:
-    ///- track_ids - a list of track URIs, URLs or IDs
+    ///- track_ids - a list of track URIs, URLs or IDs, any number of them,
+    ///  they are checked in batches of 50 and the results stay in order
     pub fn current_user_saved_tracks_contains(&self, track_ids: &[String]) -> Result<Vec<bool>, failure::Error> {
-        let uris: Vec<String> = track_ids
-            .iter()
-            .map(|id| self.get_id(Type::Track, id))
-            .collect();
-        let url = format!("me/tracks/contains/?ids={}",uris.join(","));
-        let mut dumb = HashMap::new();
-        let result = self.get(&url, &mut dumb)?;
-        self.convert_result::<Vec<bool>>(&result)
+        self.library_contains(Type::Track, track_ids)
+    }
+
+    ///query a `contains` endpoint that accepts at most 50 ids in batches,
+    ///concatenating the answers so they stay aligned with `ids`
+    fn contains_in_chunks<F: Fn(&str) -> String>(&self, ids: &[String], url: F) -> Result<Vec<bool>, failure::Error> {
+        let mut contains = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(50) {
+            let mut dumb: HashMap<String, String> = HashMap::new();
+            let result = self.get(&url(&chunk.join(",")), &mut dumb)?;
+            contains.extend(self.convert_result::<Vec<bool>>(&result)?);
+        }
+        Ok(contains)
     }
 
     ///[save tracks user ](https://developer.spotify.com/web-api/save-tracks-user/)
@@ -1054,21 +1938,84 @@ impl Spotify {
     ///Parameters:
     ///- track_ids - a list of track URIs, URLs or IDs
     pub fn current_user_saved_tracks_add(&self, track_ids: &[String]) -> Result<(), failure::Error> {
-        let uris: Vec<String> = track_ids
-            .iter()
-            .map(|id| self.get_id(Type::Track, id))
-            .collect();
-        let url = format!("me/tracks/?ids={}",uris.join(","));
-        match self.put(&url, &json!({})) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e),
+        self.library_add(Type::Track, track_ids)
+    }
+
+    ///Save the currently playing track to the current user's "Your Music"
+    ///library, or remove it when it's already saved, like a heart button.
+    ///Returns whether the track is saved now. Fails when nothing is
+    ///playing, or when an episode or a local file is playing
+    pub fn toggle_current_track_saved(&self) -> Result<bool, failure::Error> {
+        let track_id = match self.current_user_playing_track(Some(vec![Type::Track]))? {
+            Some(Playing { item: Some(PlayingItem::Track(track)), .. }) => track.id,
+            _ => None,
+        };
+        let track_ids = match track_id {
+            Some(track_id) => vec![track_id],
+            None => return Err(format_err!("no saveable track is playing")),
+        };
+        let saved = self.current_user_saved_tracks_contains(&track_ids)?
+            .first()
+            .cloned()
+            .unwrap_or(false);
+        if saved {
+            self.current_user_saved_tracks_delete(&track_ids)?;
+        } else {
+            self.current_user_saved_tracks_add(&track_ids)?;
         }
+        Ok(!saved)
+    }
+
+    ///[get users saved shows](https://developer.spotify.com/documentation/web-api/reference/library/get-users-saved-shows/)
+    ///Get a list of the shows saved in the current user's library
+    ///Parameters:
+    ///- limit - the number of shows to return, at most 50
+    ///- offset - the index of the first show to return
+    pub fn get_saved_shows<L: Into<Option<u32>>, O: Into<Option<u32>>>
+        (&self,
+         limit: L,
+         offset: O)
+         -> Result<Page<SavedShow>, failure::Error> {
+        let mut params = HashMap::new();
+        params.insert("limit".to_owned(), clamp_limit(limit.into().unwrap_or(20), 50).to_string());
+        params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
+        let url = String::from("me/shows");
+        let result = self.get(&url, &mut params)?;
+        self.convert_result::<Page<SavedShow>>(&result)
+    }
+
+    ///[save shows user](https://developer.spotify.com/documentation/web-api/reference/library/save-shows-user/)
+    ///Save one or more shows to the current user's library
+    ///Parameters:
+    ///- show_ids - a list of show URIs, URLs or IDs
+    pub fn save_shows(&self, show_ids: &[String]) -> Result<(), failure::Error> {
+        self.library_add(Type::Show, show_ids)
+    }
+
+    ///[remove shows user](https://developer.spotify.com/documentation/web-api/reference/library/remove-shows-user/)
+    ///Remove one or more shows from the current user's library
+    ///Parameters:
+    ///- show_ids - a list of show URIs, URLs or IDs
+    ///- market - an ISO 3166-1 alpha-2 country code or `Country::FromToken`
+    pub fn remove_saved_shows(&self, show_ids: &[String], market: Option<Country>) -> Result<(), failure::Error> {
+        let mut params = HashMap::new();
+        self.insert_market(&mut params, market);
+        self.library_call(Method::DELETE, Type::Show, show_ids, params)
+    }
+
+    ///[check users saved shows](https://developer.spotify.com/documentation/web-api/reference/library/check-users-saved-shows/)
+    ///Check if one or more shows is already saved in the current user's library
+    ///Parameters:
+    ///- show_ids - a list of show URIs, URLs or IDs, any number of them,
+    ///  they are checked in batches of 50 and the results stay in order
+    pub fn check_saved_shows(&self, show_ids: &[String]) -> Result<Vec<bool>, failure::Error> {
+        self.library_contains(Type::Show, show_ids)
     }
 
     ///[get users  top artists and tracks](https://developer.spotify.com/web-api/get-users-top-artists-and-tracks/)
     ///Get the current user's top artists
     ///Parameters:
-    ///- limit - the number of entities to return
+    ///- limit - the number of entities to return, at most 50
     ///- offset - the index of the first entity to return
     ///- time_range - Over what time frame are the affinities computed
 
@@ -1080,7 +2027,7 @@ impl Spotify {
          offset: O,
          time_range: T)
          -> Result<Page<FullArtist>, failure::Error> {
-        let limit = limit.into().unwrap_or(20);
+        let limit = clamp_limit(limit.into().unwrap_or(20), 50);
         let offset = offset.into().unwrap_or(0);
         let time_range = time_range.into().unwrap_or(TimeRange::MediumTerm);
         let mut params = HashMap::new();
@@ -1095,7 +2042,7 @@ impl Spotify {
     ///[get users top artists and tracks](https://developer.spotify.com/web-api/get-users-top-artists-and-tracks/)
     ///Get the current user's top tracks
     ///Parameters:
-    ///- limit - the number of entities to return
+    ///- limit - the number of entities to return, at most 50
     ///- offset - the index of the first entity to return
     ///- time_range - Over what time frame are the affinities computed
     pub fn current_user_top_tracks<L: Into<Option<u32>>,
@@ -1106,7 +2053,7 @@ impl Spotify {
          offset: O,
          time_range: T)
          -> Result<Page<FullTrack>, failure::Error> {
-        let limit = limit.into().unwrap_or(20);
+        let limit = clamp_limit(limit.into().unwrap_or(20), 50);
         let offset = offset.into().unwrap_or(0);
         let time_range = time_range.into().unwrap_or(TimeRange::MediumTerm);
         let mut params = HashMap::new();
@@ -1121,14 +2068,43 @@ impl Spotify {
     ///[get recently played](https://developer.spotify.com/web-api/web-api-personalization-endpoints/get-recently-played/)
     ///Get the current user's recently played tracks
     ///Parameters:
-    ///- limit - the number of entities to return
+    ///- limit - the number of entities to return, at most 50
     pub fn current_user_recently_played<L: Into<Option<u32>>>
         (&self,
          limit: L)
          -> Result<CursorBasedPage<PlayHistory>, failure::Error> {
-        let limit = limit.into().unwrap_or(50);
+        self.current_user_recently_played_between(limit, None, None)
+    }
+
+    ///[get recently played](https://developer.spotify.com/web-api/web-api-personalization-endpoints/get-recently-played/)
+    ///Get the current user's tracks played before or after a point in time,
+    ///at most one of `before` and `after` may be given
+    ///Parameters:
+    ///- limit - the number of entities to return, at most 50
+    ///- before - only tracks played before this time
+    ///- after - only tracks played after this time
+    pub fn current_user_recently_played_between<L: Into<Option<u32>>>
+        (&self,
+         limit: L,
+         before: Option<DateTime<Utc>>,
+         after: Option<DateTime<Utc>>)
+         -> Result<CursorBasedPage<PlayHistory>, failure::Error> {
+        let limit = clamp_limit(limit.into().unwrap_or(50), 50);
         let mut params = HashMap::new();
         params.insert("limit".to_owned(), limit.to_string());
+        match (before, after) {
+            (Some(_), Some(_)) => {
+                return Err(format_err!("only one of before and after may be specified"));
+            }
+            // the cursors are unix timestamps in milliseconds
+            (Some(before), None) => {
+                params.insert("before".to_owned(), before.timestamp_millis().to_string());
+            }
+            (None, Some(after)) => {
+                params.insert("after".to_owned(), after.timestamp_millis().to_string());
+            }
+            (None, None) => {}
+        }
         let url = String::from("me/player/recently-played");
         let result = self.get(&url, &mut params)?;
         self.convert_result::<CursorBasedPage<PlayHistory>>(&result)
@@ -1141,15 +2117,7 @@ impl Spotify {
     ///Parameters:
     ///- album_ids - a list of album URIs, URLs or IDs
     pub fn current_user_saved_albums_add(&self, album_ids: &[String]) -> Result<(), failure::Error> {
-        let uris: Vec<String> = album_ids
-            .iter()
-            .map(|id| self.get_id(Type::Album, id))
-            .collect();
-        let url = format!("me/albums/?ids={}",uris.join(","));
-        match self.put(&url, &json!({})) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e),
-        }
+        self.library_add(Type::Album, album_ids)
     }
 
     ///[remove albums user](https://developer.spotify.com/documentation/web-api/reference/library/remove-albums-user/)
@@ -1158,15 +2126,17 @@ impl Spotify {
     ///Parameters:
     ///- album_ids - a list of album URIs, URLs or IDs
     pub fn current_user_saved_albums_delete(&self, album_ids: &[String]) -> Result<(), failure::Error> {
-        let uris: Vec<String> = album_ids
-            .iter()
-            .map(|id| self.get_id(Type::Album, id))
-            .collect();
-        let url = format!("me/albums/?ids={}",uris.join(","));
-        match self.delete(&url, &json!({})) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e),
-        }
+        self.library_remove(Type::Album, album_ids)
+    }
+
+    ///[check users saved albums](https://developer.spotify.com/documentation/web-api/reference/library/check-users-saved-albums/)
+    ///Check if one or more albums is already saved in
+    ///the current Spotify user’s “Your Music” library.
+    ///Parameters:
+    ///- album_ids - a list of album URIs, URLs or IDs, any number of them,
+    ///  they are checked in batches of 50 and the results stay in order
+    pub fn current_user_saved_albums_contains(&self, album_ids: &[String]) -> Result<Vec<bool>, failure::Error> {
+        self.library_contains(Type::Album, album_ids)
     }
 
 
@@ -1195,6 +2165,26 @@ impl Spotify {
     }
 
 
+    ///[check current user follows](https://developer.spotify.com/documentation/web-api/reference/follow/check-current-user-follows/)
+    ///Check if the current user follows one or more artists
+    ///Parameters:
+    ///- artist_ids - a list of artist URIs, URLs or IDs, any number of them,
+    ///  they are checked in batches of 50 and the results stay in order
+    pub fn user_artist_check_follow(&self, artist_ids: &[String]) -> Result<Vec<bool>, failure::Error> {
+        let ids: Vec<String> = artist_ids.iter().map(|id| self.get_id(Type::Artist, id)).collect();
+        self.contains_in_chunks(&ids, |ids| format!("me/following/contains?type=artist&ids={}", ids))
+    }
+
+    ///[check current user follows](https://developer.spotify.com/documentation/web-api/reference/follow/check-current-user-follows/)
+    ///Check if the current user follows one or more users
+    ///Parameters:
+    ///- user_ids - a list of user URIs, URLs or IDs, any number of them,
+    ///  they are checked in batches of 50 and the results stay in order
+    pub fn user_check_follow_users(&self, user_ids: &[String]) -> Result<Vec<bool>, failure::Error> {
+        let ids: Vec<String> = user_ids.iter().map(|id| self.get_id(Type::User, id)).collect();
+        self.contains_in_chunks(&ids, |ids| format!("me/following/contains?type=user&ids={}", ids))
+    }
+
     ///[follow artists users](https://developer.spotify.com/web-api/follow-artists-users/)
     ///Follow one or more users
     ///Parameters:
@@ -1245,7 +2235,7 @@ impl Spotify {
          offset: O)
          -> Result<FeaturedPlaylists, failure::Error> {
         let mut params = HashMap::new();
-        let limit = limit.into().unwrap_or(20);
+        let limit = clamp_limit(limit.into().unwrap_or(20), 50);
         let offset = offset.into().unwrap_or(0);
         if let Some(_locale) = locale {
             params.insert("locale".to_owned(), _locale);
@@ -1279,7 +2269,7 @@ impl Spotify {
          offset: O)
          -> Result<PageSimpliedAlbums, failure::Error> {
         let mut params = HashMap::new();
-        let limit = limit.into().unwrap_or(20);
+        let limit = clamp_limit(limit.into().unwrap_or(20), 50);
         let offset = offset.into().unwrap_or(0);
         if let Some(_country) = country {
             params.insert("country".to_owned(), _country.as_str().to_owned());
@@ -1310,7 +2300,7 @@ impl Spotify {
                                                                   offset: O)
                                                                   -> Result<PageCategory, failure::Error> {
         let mut params = HashMap::new();
-        let limit = limit.into().unwrap_or(20);
+        let limit = clamp_limit(limit.into().unwrap_or(20), 50);
         let offset = offset.into().unwrap_or(0);
         if let Some(_locale) = locale {
             params.insert("locale".to_owned(), _locale);
@@ -1331,8 +2321,14 @@ impl Spotify {
     /// - seed_artists - a list of artist IDs, URIs or URLs
     /// - seed_tracks - a list of artist IDs, URIs or URLs
     /// - seed_genres - a list of genre names. Available genres for
-    /// - country - An ISO 3166-1 alpha-2 country code. If provided, all
-    ///   results will be playable in this country.
+    ///   recommendations can be found by calling recommendation_genre_seeds.
+    ///   Up to 5 seeds may be given in total across artists, genres and
+    ///   tracks, more or none fail with `ApiError::TooManySeeds` or
+    ///   `ApiError::NoSeeds` before anything is sent
+    /// - country - An ISO 3166-1 alpha-2 country code or `Country::FromToken`,
+    ///   sent as `market`. If provided, tracks are relinked for this country
+    ///   and each track's `is_playable` tells whether it plays there. When
+    ///   None and `auto_market` is set, the user's country is used.
     /// - limit - The maximum number of items to return. Default: 20.
     ///   Minimum: 1. Maximum: 100
     /// - min/max/target_<attribute> - For the tuneable track attributes listed
@@ -1346,8 +2342,18 @@ impl Spotify {
                                                  country: Option<Country>,
                                                  payload: &Map<String, Value>)
                                                  -> Result<Recommendations, failure::Error> {
+        let seed_count = [&seed_artists, &seed_genres, &seed_tracks]
+            .iter()
+            .filter_map(|seeds| seeds.as_ref())
+            .map(|seeds| seeds.len())
+            .sum();
+        match seed_count {
+            0 => return Err(failure::Error::from(ApiError::NoSeeds)),
+            1..=5 => {}
+            _ => return Err(failure::Error::from(ApiError::TooManySeeds(seed_count))),
+        }
         let mut params = HashMap::new();
-        let limit = limit.into().unwrap_or(20);
+        let limit = clamp_limit(limit.into().unwrap_or(20), 100);
         params.insert("limit".to_owned(), limit.to_string());
         if let Some(_seed_artists) = seed_artists {
             let seed_artists_ids: Vec<String> = _seed_artists
@@ -1357,6 +2363,9 @@ impl Spotify {
             params.insert("seed_artists".to_owned(), seed_artists_ids.join(","));
         }
         if let Some(_seed_genres) = seed_genres {
+            if self.validate_genre_seeds {
+                self.check_genre_seeds(&_seed_genres)?;
+            }
             params.insert("seed_genres".to_owned(), _seed_genres.join(","));
         }
         if let Some(_seed_tracks) = seed_tracks {
@@ -1366,9 +2375,7 @@ impl Spotify {
                 .collect();
             params.insert("seed_tracks".to_owned(), seed_tracks_ids.join(","));
         }
-        if let Some(_country) = country {
-            params.insert("market".to_owned(), _country.as_str().to_owned());
-        }
+        self.insert_market(&mut params, country);
         let attributes = vec!["acousticness",
                           "danceability",
                           "duration_ms",
@@ -1405,6 +2412,41 @@ impl Spotify {
         self.convert_result::<Recommendations>(&result)
 
     }
+    ///[get available markets](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-available-markets)
+    ///Get the ISO 3166-1 alpha-2 codes of the countries where Spotify is
+    ///available, e.g. to let users pick a market without a hardcoded list
+    pub fn available_markets(&self) -> Result<Vec<String>, failure::Error> {
+        let url = String::from("markets");
+        let result = self.get(&url, &mut HashMap::new())?;
+        self.convert_result::<Markets>(&result).map(|markets| markets.markets)
+    }
+
+    ///[get available genre seeds](https://developer.spotify.com/documentation/web-api/reference/browse/get-recommendations/)
+    ///Retrieve a list of available genres seed parameter values for
+    ///recommendations.
+    pub fn recommendation_genre_seeds(&self) -> Result<Vec<String>, failure::Error> {
+        let url = String::from("recommendations/available-genre-seeds");
+        let mut dumb: HashMap<String, String> = HashMap::new();
+        let result = self.get(&url, &mut dumb)?;
+        self.convert_result::<GenreSeeds>(&result).map(|seeds| seeds.genres)
+    }
+
+    ///Fail with `ApiError::InvalidGenreSeed` for the first genre that isn't an
+    ///available genre seed. The available seeds are fetched once and cached.
+    ///Parameters:
+    ///- genres - genre names to check
+    pub fn check_genre_seeds(&self, genres: &[String]) -> Result<(), failure::Error> {
+        let mut genre_seeds = self.genre_seeds.lock().unwrap();
+        if genre_seeds.is_none() {
+            *genre_seeds = Some(self.recommendation_genre_seeds()?);
+        }
+        let available = genre_seeds.as_ref().unwrap();
+        match genres.iter().find(|genre| !available.contains(genre)) {
+            Some(genre) => Err(failure::Error::from(ApiError::InvalidGenreSeed(genre.to_owned()))),
+            None => Ok(()),
+        }
+    }
+
     ///[get audio features](https://developer.spotify.com/web-api/get-audio-features/)
     ///Get audio features for a track
     ///- track - track URI, URL or ID
@@ -1424,7 +2466,7 @@ impl Spotify {
             .iter()
             .map(|track| self.get_id(Type::Track, track))
             .collect();
-        let url = format!("audio-features/?ids={}",ids.join(","));
+        let url = format!("audio-features?ids={}",ids.join(","));
         let mut dumb = HashMap::new();
         match self.get(&url, &mut dumb) {
             Ok(result) => {
@@ -1439,6 +2481,50 @@ impl Spotify {
 
     }
 
+    ///Get every track of a playlist together with its audio features
+    ///Tracks are fetched page by page and their features are looked up in
+    ///batches of 100, then matched back by track ID so the pairs stay aligned.
+    ///Local tracks and tracks without audio features are skipped.
+    ///Parameters:
+    ///- user_id - the id of the user
+    ///- playlist_id - the id of the playlist
+    pub fn playlist_audio_features(&self,
+                                   user_id: &str,
+                                   playlist_id: &str)
+                                   -> Result<Vec<(FullTrack, AudioFeatures)>, failure::Error> {
+        let mut tracks: Vec<FullTrack> = vec![];
+        let mut offset = 0;
+        loop {
+            let page = self.user_playlist_tracks(user_id, playlist_id, None, 100, offset, None)?;
+            offset += page.items.len() as u32;
+            tracks.extend(page.items.into_iter().filter_map(|item| item.track));
+            if page.next.is_none() {
+                break;
+            }
+        }
+        tracks.retain(|track| track.id.is_some());
+
+        let mut features: HashMap<String, AudioFeatures> = HashMap::new();
+        for chunk in tracks.chunks(100) {
+            let ids: Vec<String> = chunk
+                .iter()
+                .filter_map(|track| track.id.clone())
+                .collect();
+            if let Some(payload) = self.audios_features(&ids)? {
+                for feature in payload.audio_features {
+                    features.insert(feature.id.clone(), feature);
+                }
+            }
+        }
+        Ok(tracks
+               .into_iter()
+               .filter_map(|track| {
+                   let feature = track.id.as_ref().and_then(|id| features.get(id).cloned());
+                   feature.map(|feature| (track, feature))
+               })
+               .collect())
+    }
+
     ///[get audio analysis](https://developer.spotify.com/web-api/get-audio-analysis/)
     ///Get Audio Analysis for a Track
     ///Parameters:
@@ -1462,48 +2548,44 @@ impl Spotify {
 
     ///[get informatation about the users  current playback](https://developer.spotify.com/web-api/get-information-about-the-users-current-playback/)
     ///Get Information About The User’s Current Playback
+    ///Returns `Ok(None)` when the user has no active device, while failing
+    ///to reach Spotify is an `Err`.
     ///        Parameters:
     ///        - market - an ISO 3166-1 alpha-2 country code.
-    pub fn current_playback(&self, market: Option<Country>) -> Result<Option<FullPlayingContext>, failure::Error> {
+    ///        - additional_types - item types besides tracks to return,
+    ///          defaults to tracks and episodes.
+    pub fn current_playback(&self,
+                            market: Option<Country>,
+                            additional_types: Option<Vec<Type>>)
+                            -> Result<Option<FullPlayingContext>, failure::Error> {
         let url = String::from("me/player");
         let mut params = HashMap::new();
-        if let Some(_market) = market {
-            params.insert("country".to_owned(), _market.as_str().to_owned());
-        }
-        match self.get(&url, &mut params) {
-            Ok(result) => {
-                if result.is_empty() {
-                    Ok(None)
-                } else {
-                    self.convert_result::<Option<FullPlayingContext>>(&result)
-                }
-            }
-            Err(e) => Err(e),
-        }
+        params.insert("additional_types".to_owned(), self.additional_types(additional_types));
+        self.insert_market(&mut params, market);
+        // 204 No Content when no device is active converts to None
+        let result = self.get(&url, &mut params)?;
+        self.convert_result::<Option<FullPlayingContext>>(&result)
     }
 
     ///[get the users currently playing track](https://developer.spotify.com/web-api/get-the-users-currently-playing-track/)
     /// Get the User’s Currently Playing Track
+    ///Returns `Ok(None)` when nothing is playing on any device, while
+    ///failing to reach Spotify is an `Err`.
     ///        Parameters:
     ///        - market - an ISO 3166-1 alpha-2 country code.
+    ///        - additional_types - item types besides tracks to return,
+    ///          defaults to tracks and episodes.
     pub fn current_playing(&self,
-                           market: Option<Country>)
+                           market: Option<Country>,
+                           additional_types: Option<Vec<Type>>)
                            -> Result<Option<SimplifiedPlayingContext>, failure::Error> {
         let url = String::from("me/player/currently-playing");
         let mut params = HashMap::new();
-        if let Some(_market) = market {
-            params.insert("country".to_owned(), _market.as_str().to_owned());
-        }
-        match self.get(&url, &mut params) {
-            Ok(result) => {
-                if result.is_empty() {
-                    Ok(None)
-                } else {
-                    self.convert_result::<Option<SimplifiedPlayingContext>>(&result)
-                }
-            }
-            Err(e) => Err(e),
-        }
+        params.insert("additional_types".to_owned(), self.additional_types(additional_types));
+        self.insert_market(&mut params, market);
+        // 204 No Content when no device is active converts to None
+        let result = self.get(&url, &mut params)?;
+        self.convert_result::<Option<SimplifiedPlayingContext>>(&result)
     }
     ///[transfer a users playback](https://developer.spotify.com/web-api/transfer-a-users-playback/)
     ///Transfer a User’s Playback
@@ -1585,6 +2667,34 @@ impl Spotify {
 
     }
 
+    ///[start a users playback](https://developer.spotify.com/web-api/start-a-users-playback/)
+    ///Start playback of an album, artist or playlist
+    ///Parameters:
+    ///- context_uri - spotify uri of the album, artist or playlist to play
+    ///- device_id - device target for playback
+    ///- offset - track of the context to start with, by index or track uri
+    pub fn start_context_playback(&self,
+                                  context_uri: &str,
+                                  device_id: Option<String>,
+                                  offset: Option<super::model::offset::Offset>)
+                                  -> Result<(), failure::Error> {
+        self.start_playback(device_id, Some(context_uri.to_owned()), None, offset, None)
+    }
+
+    ///[start a users playback](https://developer.spotify.com/web-api/start-a-users-playback/)
+    ///Start playback of a list of tracks or episodes
+    ///Parameters:
+    ///- uris - spotify uris of the tracks or episodes to play
+    ///- device_id - device target for playback
+    ///- offset - item of `uris` to start with, by index or uri
+    pub fn start_uris_playback(&self,
+                               uris: Vec<String>,
+                               device_id: Option<String>,
+                               offset: Option<super::model::offset::Offset>)
+                               -> Result<(), failure::Error> {
+        self.start_playback(device_id, None, Some(uris), offset, None)
+    }
+
     ///[pause a users playback](https://developer.spotify.com/web-api/pause-a-users-playback/)
     ///Pause a User’s Playback
     ///Parameters:
@@ -1598,6 +2708,28 @@ impl Spotify {
 
     }
 
+    ///Stop playback as far as the API allows: pause it, and do nothing when
+    ///nothing is playing, where a plain `pause_playback` fails with 403.
+    ///The API has no real stop, playback stays on the device and can't be
+    ///moved to no device, and the queue can't be cleared either
+    ///Parameters:
+    ///- device_id - device target for playback
+    pub fn stop_playback(&self, device_id: Option<String>) -> Result<(), failure::Error> {
+        match self.current_playback(None, None)? {
+            Some(FullPlayingContext { is_playing: true, .. }) => self.pause_playback(device_id),
+            _ => Ok(()),
+        }
+    }
+
+    ///[get the users queue](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-queue)
+    ///Get the currently playing item and the tracks and episodes queued
+    ///after it
+    pub fn queue(&self) -> Result<Queue, failure::Error> {
+        let url = String::from("me/player/queue");
+        let result = self.get(&url, &mut HashMap::new())?;
+        self.convert_result::<Queue>(&result)
+    }
+
     ///[skip users playback to the next track](https://developer.spotify.com/web-api/skip-users-playback-to-next-track/)
     ///Skip User’s Playback To Next Track
     ///            Parameters:
@@ -1625,7 +2757,8 @@ impl Spotify {
     ///[seek-to-position-in-currently-playing-track/](https://developer.spotify.com/web-api/seek-to-position-in-currently-playing-track/)
     ///Seek To Position In Currently Playing Track
     ///            Parameters:
-    /// - position_ms - position in milliseconds to seek to
+    /// - position_ms - position in milliseconds to seek to, it isn't checked
+    ///   against the track's duration, see `seek_fraction` for that
     /// - device_id - device target for playback
     pub fn seek_track(&self, position_ms: u32, device_id: Option<String>) -> Result<(), failure::Error> {
         let url = self.append_device_id(&format!("me/player/seek?position_ms={}",position_ms),
@@ -1637,6 +2770,33 @@ impl Spotify {
 
     }
 
+    ///[seek-to-position-in-currently-playing-track/](https://developer.spotify.com/web-api/seek-to-position-in-currently-playing-track/)
+    ///Seek to a position given as a `Duration`
+    ///            Parameters:
+    /// - position - position to seek to
+    /// - device_id - device target for playback
+    pub fn seek_to(&self, position: Duration, device_id: Option<String>) -> Result<(), failure::Error> {
+        let position_ms = position.as_secs() * 1000 + u64::from(position.subsec_millis());
+        self.seek_track(position_ms.min(u64::from(u32::MAX)) as u32, device_id)
+    }
+
+    ///[seek-to-position-in-currently-playing-track/](https://developer.spotify.com/web-api/seek-to-position-in-currently-playing-track/)
+    ///Seek to a fraction of the currently playing item, e.g. 0.5 for its
+    ///middle. The duration is looked up with `current_playback` and the
+    ///position is clamped to stay before the end of the item.
+    ///            Parameters:
+    /// - fraction - between 0.0 and 1.0, clamped to that range
+    /// - device_id - device target for playback
+    pub fn seek_fraction(&self, fraction: f32, device_id: Option<String>) -> Result<(), failure::Error> {
+        let duration_ms = match self.current_playback(None, None)? {
+            Some(FullPlayingContext { item: Some(item), .. }) => item.duration_ms(),
+            _ => return Err(format_err!("nothing is playing, can't seek to a fraction of it")),
+        };
+        let fraction = if fraction.is_nan() { 0.0 } else { fraction.clamp(0.0, 1.0) };
+        let position_ms = (f64::from(duration_ms) * f64::from(fraction)) as u32;
+        self.seek_track(position_ms.min(duration_ms.saturating_sub(1)), device_id)
+    }
+
     ///[set repeat mode on users playback](https://developer.spotify.com/web-api/set-repeat-mode-on-users-playback/)
     ///Set Repeat Mode On User’s Playback
     ///            Parameters:
@@ -1685,11 +2845,92 @@ impl Spotify {
     }
 
     pub fn convert_result<'a, T: Deserialize<'a>>(&self, input: &'a str) -> Result<T, failure::Error> {
+        // 204 No Content responses have an empty body, treat it as `null` so
+        // that it converts to `()` or `None` instead of failing to parse
+        let input = if input.trim().is_empty() { "null" } else { input };
         let result = serde_json::from_str::<T>(input)
             .map_err(|e| format_err!("convert result failed, reason: {:?}; content: [{:?}]", e,input))?;
         Ok(result)
     }
 
+    ///Fetch the object a spotify URI or URL points to, e.g.
+    ///`spotify:album:0sNOF9WDwhWunNAHPD3Baj` or
+    ///`https://open.spotify.com/track/6rqhFgbbKwnb9MLmUQDhG6?si=...`
+    ///Parameters:
+    ///- uri - a track, album, artist, playlist or user URI or URL
+    pub fn resolve(&self, uri: &str) -> Result<SpotifyItem, failure::Error> {
+        let path = uri.split('?').next().unwrap_or("");
+        let fields: Vec<&str> = path.split(&[':', '/'][..]).collect();
+        let len = fields.len();
+        // case-insensitive like `get_id`, e.g. `open.spotify.com/Track/...`
+        let _type = if len >= 3 {
+            Type::from_str(&fields[len - 2].to_lowercase())
+        } else {
+            None
+        };
+        match _type {
+            Some(Type::Track) => self.track(uri).map(|track| SpotifyItem::Track(Box::new(track))),
+            Some(Type::Album) => self.album(uri).map(|album| SpotifyItem::Album(Box::new(album))),
+            Some(Type::Artist) => self.artist(uri).map(|artist| SpotifyItem::Artist(Box::new(artist))),
+            Some(Type::Playlist) => {
+                self.playlist(uri, None, None)
+                    .map(|playlist| SpotifyItem::Playlist(Box::new(playlist)))
+            }
+            Some(Type::User) => {
+                self.user(fields[len - 1]).map(|user| SpotifyItem::User(Box::new(user)))
+            }
+            Some(Type::Show) => self.show(uri, None).map(|show| SpotifyItem::Show(Box::new(show))),
+            Some(Type::Episode) => {
+                self.episode(uri, None).map(|episode| SpotifyItem::Episode(Box::new(episode)))
+            }
+            None => Err(format_err!("couldn't find the type of spotify uri {:?}", uri)),
+        }
+    }
+
+    ///`additional_types` query value of the player endpoints, tracks and
+    ///episodes unless given
+    fn additional_types(&self, additional_types: Option<Vec<Type>>) -> String {
+        additional_types
+            .unwrap_or_else(|| vec![Type::Track, Type::Episode])
+            .iter()
+            .map(|_type| _type.as_str())
+            .join(",")
+    }
+
+    ///Fall back to `default_market`, or the current user's country when
+    ///`auto_market` is enabled, when no market was given.
+    fn market_or_default(&self, market: Option<Country>) -> Option<Country> {
+        let market = market.or(self.default_market);
+        if market.is_some() || !self.auto_market {
+            return market;
+        }
+        if let Some(user_market) = *self.user_market.lock().unwrap() {
+            return user_market;
+        }
+        // not holding the lock during the request, concurrent callers may
+        // look the country up twice but don't wait on each other; failures
+        // aren't cached so the next call tries again
+        match self.me() {
+            Ok(user) => {
+                let country = user.country.and_then(|country| Country::from_str(&country));
+                *self.user_market.lock().unwrap() = Some(country);
+                country
+            }
+            Err(e) => {
+                warn!("couldn't look up the user's country for auto_market: {}", e);
+                None
+            }
+        }
+    }
+
+    ///Add the `market` query parameter resolved by `market_or_default`,
+    ///leaving it out when there is none
+    fn insert_market(&self, params: &mut HashMap<String, String>, market: Option<Country>) {
+        if let Some(_market) = self.market_or_default(market) {
+            params.insert("market".to_owned(), _market.as_str().to_owned());
+        }
+    }
+
     ///Append device ID to API path.
     fn append_device_id(&self, path: &str, device_id: Option<String>) -> String {
         let mut new_path = path.to_string();
@@ -1704,6 +2945,15 @@ impl Spotify {
     }
 
     fn get_uri(&self, _type: Type, _id: &str) -> String {
+        // keep complete URIs as they are, including legacy ones with a user
+        // segment such as `spotify:user:{user_id}:playlist:{playlist_id}`
+        if _id.starts_with("spotify:") {
+            let fields: Vec<&str> = _id.split(':').collect();
+            let len = fields.len();
+            if len >= 3 && fields[len - 2] == _type.as_str() && !fields[len - 1].is_empty() {
+                return _id.to_owned();
+            }
+        }
         let mut uri = String::from("spotify:");
         uri.push_str(_type.as_str());
         uri.push(':');
@@ -1715,8 +2965,10 @@ impl Spotify {
         let mut _id = id.to_owned().clone();
         let fields: Vec<&str> = _id.split(':').collect();
         let len = fields.len();
+        // the type segment is the second to last one in both the modern form
+        // and the legacy `spotify:user:{user}:playlist:{id}` form
         if len >= 3 {
-            if _type.as_str() != fields[len - 2] {
+            if !_type.as_str().eq_ignore_ascii_case(fields[len - 2]) {
                 error!("expected id of type {:?} but found type {:?} {:?}",
                                         _type,
                                         fields[len - 2],
@@ -1725,10 +2977,12 @@ impl Spotify {
                 return fields[len - 1].to_owned();
             }
         }
-        let sfields: Vec<&str> = _id.split('/').collect();
+        // share links usually carry a tracking query string, e.g. `?si=...`
+        let path = _id.split('?').next().unwrap_or("");
+        let sfields: Vec<&str> = path.split('/').collect();
         let len: usize = sfields.len();
         if len >= 3 {
-            if _type.as_str() != sfields[len - 2] {
+            if !_type.as_str().eq_ignore_ascii_case(sfields[len - 2]) {
                 error!(
                                         "expected id of type {:?} but found type {:?} {:?}",
                                         _type,
@@ -1746,6 +3000,636 @@ impl Spotify {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::search::SearchQuery;
+
+    /// answers every request with `status` and `body`, recording the URLs
+    struct StubTransport {
+        status: StatusCode,
+        body: String,
+        urls: Arc<Mutex<Vec<String>>>,
+    }
+    impl HttpTransport for StubTransport {
+        fn call(&self,
+                method: &Method,
+                url: &str,
+                _body: Option<&Value>,
+                _headers: &HeaderMap)
+                -> Result<TransportResponse, failure::Error> {
+            self.urls.lock().unwrap().push(format!("{} {}", method, url));
+            Ok(TransportResponse {
+                status: self.status,
+                headers: HeaderMap::new(),
+                body: self.body.clone(),
+            })
+        }
+    }
+    /// answers every request with 204, recording the request headers
+    struct HeadersTransport(Arc<Mutex<Vec<HeaderMap>>>);
+    impl HttpTransport for HeadersTransport {
+        fn call(&self,
+                _method: &Method,
+                _url: &str,
+                _body: Option<&Value>,
+                headers: &HeaderMap)
+                -> Result<TransportResponse, failure::Error> {
+            self.0.lock().unwrap().push(headers.clone());
+            Ok(TransportResponse {
+                status: StatusCode::NO_CONTENT,
+                headers: HeaderMap::new(),
+                body: String::new(),
+            })
+        }
+    }
+    /// answers with the queued responses in order, recording the requests
+    struct SequenceTransport {
+        responses: Mutex<Vec<(StatusCode, String)>>,
+        urls: Arc<Mutex<Vec<String>>>,
+    }
+    impl HttpTransport for SequenceTransport {
+        fn call(&self,
+                method: &Method,
+                url: &str,
+                _body: Option<&Value>,
+                _headers: &HeaderMap)
+                -> Result<TransportResponse, failure::Error> {
+            self.urls.lock().unwrap().push(format!("{} {}", method, url));
+            let (status, body) = self.responses.lock().unwrap().remove(0);
+            Ok(TransportResponse {
+                status,
+                headers: HeaderMap::new(),
+                body,
+            })
+        }
+    }
+    fn sequence_spotify(responses: Vec<(StatusCode, &str)>) -> (Spotify, Arc<Mutex<Vec<String>>>) {
+        let urls = Arc::new(Mutex::new(vec![]));
+        let transport = SequenceTransport {
+            responses: Mutex::new(responses
+                .into_iter()
+                .map(|(status, body)| (status, body.to_owned()))
+                .collect()),
+            urls: urls.clone(),
+        };
+        let spotify = Spotify::default().access_token("test-access").transport(transport).build();
+        (spotify, urls)
+    }
+    fn headers_spotify() -> (Spotify, Arc<Mutex<Vec<HeaderMap>>>) {
+        let headers = Arc::new(Mutex::new(vec![]));
+        let spotify = Spotify::default()
+            .access_token("test-access")
+            .transport(HeadersTransport(headers.clone()))
+            .build();
+        (spotify, headers)
+    }
+    fn stub_spotify(status: StatusCode, body: &str) -> (Spotify, Arc<Mutex<Vec<String>>>) {
+        let urls = Arc::new(Mutex::new(vec![]));
+        let transport = StubTransport {
+            status,
+            body: body.to_owned(),
+            urls: urls.clone(),
+        };
+        let spotify = Spotify::default().access_token("test-access").transport(transport).build();
+        (spotify, urls)
+    }
+
+    #[test]
+    fn test_stub_transport() {
+        let (spotify, urls) = stub_spotify(StatusCode::OK, r#"{"genres": ["rock", "jazz"]}"#);
+        assert_eq!(spotify.recommendation_genre_seeds().unwrap(), vec!["rock", "jazz"]);
+        assert_eq!(*urls.lock().unwrap(),
+                   vec!["GET https://api.spotify.com/v1/recommendations/available-genre-seeds"]);
+
+        let (spotify, _) = stub_spotify(StatusCode::UNAUTHORIZED, "");
+        let err = spotify.recommendation_genre_seeds().unwrap_err();
+        match err.downcast_ref::<ApiError>() {
+            Some(ApiError::Unauthorized) => {}
+            _ => panic!("expected ApiError::Unauthorized, got {}", err),
+        }
+    }
+
+    /// the request `call` sends, as the URL without query and the sorted
+    /// query parameters
+    fn requested<F: FnOnce(&Spotify)>(call: F) -> (String, Vec<String>) {
+        let (spotify, urls) = stub_spotify(StatusCode::OK, "{}");
+        call(&spotify);
+        let urls = urls.lock().unwrap();
+        assert_eq!(urls.len(), 1, "expected a single request, got {:?}", *urls);
+        let mut parts = urls[0].splitn(2, '?');
+        let path = parts.next().unwrap().to_owned();
+        let mut params: Vec<String> = parts
+            .next()
+            .unwrap_or("")
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| param.to_owned())
+            .collect();
+        params.sort();
+        (path, params)
+    }
+
+    #[test]
+    fn test_check_follow_urls() {
+        let (path, params) = requested(|spotify| {
+            let ids = vec!["spotify:artist:a".to_owned(), "https://open.spotify.com/artist/b?si=x".to_owned()];
+            let _ = spotify.user_artist_check_follow(&ids);
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/me/following/contains");
+        assert_eq!(params, vec!["ids=a,b", "type=artist"]);
+
+        let (_, params) = requested(|spotify| {
+            let _ = spotify.user_check_follow_users(&["spotify:user:c".to_owned(), "d".to_owned()]);
+        });
+        assert_eq!(params, vec!["ids=c,d", "type=user"]);
+    }
+
+    #[test]
+    fn test_tracks_url() {
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.tracks(vec!["spotify:track:a", "b"], Some(Country::Sweden));
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/tracks");
+        assert_eq!(params, vec!["ids=a,b", "market=SE"]);
+    }
+
+    #[test]
+    fn test_tracks_concurrent() {
+        let (spotify, urls) = stub_spotify(StatusCode::OK, r#"{"tracks": []}"#);
+        let ids: Vec<String> = (0..120).map(|i| format!("track{}", i)).collect();
+        assert!(spotify.tracks_concurrent(ids, 2, None).unwrap().is_empty());
+        let mut urls = urls.lock().unwrap().clone();
+        urls.sort();
+        assert_eq!(urls.len(), 3);
+        assert!(urls[0].starts_with("GET https://api.spotify.com/v1/tracks?ids=track0,track1,"));
+        assert!(urls[1].contains("ids=track100,"));
+
+        let (spotify, urls) = stub_spotify(StatusCode::TOO_MANY_REQUESTS, "");
+        let ids: Vec<String> = (0..500).map(|i| format!("track{}", i)).collect();
+        let err = spotify.tracks_concurrent(ids, 1, None).unwrap_err();
+        assert!(matches!(err.downcast_ref::<ApiError>(), Some(ApiError::RateLimited(_))));
+        assert_eq!(urls.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_artist_albums_url() {
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.artist_albums("spotify:artist:a",
+                                          Some(AlbumType::Single),
+                                          Some(Country::Sweden),
+                                          Some(10),
+                                          Some(20));
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/artists/a/albums");
+        assert_eq!(params, vec!["album_type=single", "country=SE", "limit=10", "offset=20"]);
+    }
+
+    #[test]
+    fn test_album_track_url() {
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.album_track("a", 5, None, None);
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/albums/a/tracks");
+        assert_eq!(params, vec!["limit=5", "offset=0"]);
+    }
+
+    #[test]
+    fn test_search_url() {
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.search_track("abba", 10, 0, Some(Country::FromToken));
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/search");
+        assert_eq!(params, vec!["limit=10", "market=from_token", "offset=0", "q=abba", "type=track"]);
+    }
+
+    #[test]
+    fn test_search_query_url() {
+        let (_, params) = requested(|spotify| {
+            let q = SearchQuery::default().keyword("rock & roll").year(1970).build();
+            let _ = spotify.search_album(&q, 10, 0, None);
+        });
+        assert_eq!(params, vec!["limit=10", "offset=0", "q=rock+%26+roll+year%3A1970", "type=album"]);
+    }
+
+    #[test]
+    fn test_limit_clamped() {
+        let (_, params) = requested(|spotify| {
+            let _ = spotify.current_user_saved_tracks(500, 0);
+        });
+        assert_eq!(params, vec!["limit=50", "offset=0"]);
+        let (_, params) = requested(|spotify| {
+            let _ = spotify.user_playlist_tracks("u", "p", None, 500, None, None);
+        });
+        assert_eq!(params, vec!["limit=100", "offset=0"]);
+        assert_eq!(clamp_limit(0, 50), 1);
+    }
+
+    #[test]
+    fn test_search_non_ascii_url() {
+        let (_, params) = requested(|spotify| {
+            let _ = spotify.search_track("夜に駆ける", 10, 0, None);
+        });
+        assert!(params.contains(&"q=%E5%A4%9C%E3%81%AB%E9%A7%86%E3%81%91%E3%82%8B".to_owned()));
+        let (_, params) = requested(|spotify| {
+            let _ = spotify.search_playlist("حبيبي 💃", 10, 0, None);
+        });
+        assert!(params.contains(&"q=%D8%AD%D8%A8%D9%8A%D8%A8%D9%8A+%F0%9F%92%83".to_owned()));
+    }
+
+    #[test]
+    fn test_user_playlist_tracks_url() {
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.user_playlist_tracks("u", "spotify:playlist:p", Some("total"), None, None, None);
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/users/u/playlists/p/tracks");
+        assert_eq!(params, vec!["fields=total", "limit=100", "offset=0"]);
+    }
+
+    #[test]
+    fn test_saved_tracks_contains_url() {
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.current_user_saved_tracks_contains(&["a".to_owned(), "b".to_owned()]);
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/me/tracks/contains");
+        assert_eq!(params, vec!["ids=a,b"]);
+    }
+
+    #[test]
+    fn test_list_endpoint_urls() {
+        let ids = vec!["a".to_owned(), "b".to_owned()];
+        let (path, _) = requested(|spotify| {
+            let _ = spotify.artists(ids.clone());
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/artists");
+        let (path, _) = requested(|spotify| {
+            let _ = spotify.albums(ids.clone());
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/albums");
+        let (path, _) = requested(|spotify| {
+            let _ = spotify.episodes(ids.clone(), None);
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/episodes");
+        let (path, _) = requested(|spotify| {
+            let _ = spotify.audios_features(&ids);
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/audio-features");
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.current_user_saved_albums_add(&ids);
+        });
+        assert_eq!(path, "PUT https://api.spotify.com/v1/me/albums");
+        assert_eq!(params, vec!["ids=a%2Cb"]);
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.me();
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/me");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_player_additional_types_url() {
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.current_user_playing_track(None);
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/me/player/currently-playing");
+        assert_eq!(params, vec!["additional_types=track%2Cepisode"]);
+        let (_, params) = requested(|spotify| {
+            let _ = spotify.current_playback(None, Some(vec![Type::Track]));
+        });
+        assert_eq!(params, vec!["additional_types=track"]);
+    }
+
+    #[test]
+    fn test_artist_albums_all_dedup() {
+        let album = |id: &str, name: &str| {
+            json!({
+                "artists": [], "external_urls": {}, "href": null, "id": id, "images": [],
+                "name": name, "release_date": "2019-10-11", "type": "album", "uri": null
+            })
+        };
+        let page = json!({
+            "href": "", "limit": 50, "next": null, "offset": 0, "previous": null, "total": 3,
+            "items": [album("a", "Birdy"), album("b", "birdy"), album("a", "Birdy"),
+                      album("c", "Fire Within")]
+        });
+        let (spotify, _) = stub_spotify(StatusCode::OK, &page.to_string());
+        assert_eq!(spotify.artist_albums_all("x", None, None, None).unwrap().len(), 4);
+        // the single and the album "Birdy" have different ids and are both kept
+        let albums = spotify.artist_albums_all("x", None, None, true).unwrap();
+        let ids: Vec<_> = albums.iter().map(|album| album.id.clone().unwrap()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_artist_discography_urls() {
+        let body = r#"{"href": "", "items": [], "limit": 50, "next": null, "offset": 0,
+                       "previous": null, "total": 0}"#;
+        let (spotify, urls) = stub_spotify(StatusCode::OK, body);
+        let discography = spotify.artist_discography("spotify:artist:a", None).unwrap();
+        assert!(discography.albums.is_empty());
+        let album_types: Vec<String> = urls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|url| url.split('&').find(|param| param.contains("album_type=")))
+            .map(|param| param.rsplit('=').next().unwrap().to_owned())
+            .collect();
+        assert_eq!(album_types, vec!["album", "single", "compilation", "appears_on"]);
+    }
+
+    #[test]
+    fn test_library_urls() {
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.library_add(Type::Episode, &["spotify:episode:a".to_owned()]);
+        });
+        assert_eq!(path, "PUT https://api.spotify.com/v1/me/episodes");
+        assert_eq!(params, vec!["ids=a"]);
+        let (_, params) = requested(|spotify| {
+            let _ = spotify.library_remove(Type::Track, &["a".to_owned(), "b".to_owned()]);
+        });
+        assert_eq!(params, vec!["ids=a%2Cb"]);
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.current_user_saved_albums_contains(&["b".to_owned()]);
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/me/albums/contains");
+        assert_eq!(params, vec!["ids=b"]);
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.remove_saved_shows(&["c".to_owned()], Some(Country::Sweden));
+        });
+        assert_eq!(path, "DELETE https://api.spotify.com/v1/me/shows");
+        assert_eq!(params, vec!["ids=c", "market=SE"]);
+
+        // the market goes through insert_market, so default_market applies
+        let (spotify, urls) = stub_spotify(StatusCode::OK, "");
+        let spotify = spotify.market(Country::Sweden);
+        let _ = spotify.remove_saved_shows(&["c".to_owned()], None);
+        let _ = spotify.save_shows(&["c".to_owned()]);
+        let urls = urls.lock().unwrap();
+        assert!(urls[0].starts_with("DELETE https://api.spotify.com/v1/me/shows?"));
+        assert!(urls[0].contains("market=SE&"));
+        assert!(!urls[1].contains("market"));
+
+        let (spotify, urls) = stub_spotify(StatusCode::OK, "");
+        assert!(spotify.library_add(Type::Artist, &["d".to_owned()]).is_err());
+        assert!(urls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_playlist_snapshot_id() {
+        let (spotify, urls) = stub_spotify(StatusCode::OK, r#"{"snapshot_id": "MTIs"}"#);
+        assert_eq!(spotify.playlist_snapshot_id("u", "spotify:playlist:p").unwrap(), "MTIs");
+        assert_eq!(*urls.lock().unwrap(),
+                   vec!["GET https://api.spotify.com/v1/users/u/playlists/p?fields=snapshot_id&"]);
+    }
+
+    #[test]
+    fn test_get_page() {
+        let body = r#"{"href": "https://api.spotify.com/v1/me/albums?offset=20&limit=20",
+                       "items": [], "limit": 20, "next": null, "offset": 20,
+                       "previous": "https://api.spotify.com/v1/me/albums?offset=0&limit=20",
+                       "total": 20}"#;
+        let (spotify, urls) = stub_spotify(StatusCode::OK, body);
+        let page = spotify.get_page::<SavedAlbum>("https://api.spotify.com/v1/me/albums?offset=20&limit=20")
+            .unwrap();
+        assert_eq!(page.offset, 20);
+        assert!(page.next.is_none());
+        assert_eq!(*urls.lock().unwrap(),
+                   vec!["GET https://api.spotify.com/v1/me/albums?offset=20&limit=20"]);
+    }
+
+    #[test]
+    fn test_remove_tracks_snapshot_conflict() {
+        let body = r#"{"error": {"status": 400, "message": "Invalid snapshot id"}}"#;
+        let (spotify, _) = stub_spotify(StatusCode::BAD_REQUEST, body);
+        let err = spotify.user_playlist_remove_tracks_by_position("u", "p", &[0], "MTIs").unwrap_err();
+        assert!(matches!(err.downcast_ref::<ApiError>(), Some(ApiError::SnapshotConflict)));
+    }
+
+    #[test]
+    fn test_remove_tracks_other_bad_request() {
+        let body = r#"{"error": {"status": 400, "message": "Index out of bounds"}}"#;
+        let (spotify, _) = stub_spotify(StatusCode::BAD_REQUEST, body);
+        let err = spotify.user_playlist_remove_tracks_by_position("u", "p", &[99], "MTIs").unwrap_err();
+        assert!(matches!(err.downcast_ref::<ApiError>(), Some(ApiError::Other(400))));
+    }
+
+    #[test]
+    fn test_user_playlist_if_changed() {
+        let (spotify, urls) = stub_spotify(StatusCode::OK, r#"{"snapshot_id": "MTIs"}"#);
+        assert!(spotify.user_playlist_if_changed("u", "p", "MTIs", None).unwrap().is_none());
+        assert_eq!(urls.lock().unwrap().len(), 1);
+
+        // a changed snapshot fetches the whole playlist
+        let playlist = include_str!("../../tests/fixtures/playlist.json");
+        let (spotify, urls) = sequence_spotify(vec![(StatusCode::OK, r#"{"snapshot_id": "MTIs"}"#),
+                                                    (StatusCode::OK, playlist)]);
+        let changed = spotify.user_playlist_if_changed("u", "p", "MTEs", None).unwrap().unwrap();
+        let expected: FullPlaylist = serde_json::from_str(playlist).unwrap();
+        assert_eq!(changed, expected);
+        assert_eq!(urls.lock().unwrap()[1], "GET https://api.spotify.com/v1/users/u/playlists/p");
+    }
+
+    #[test]
+    fn test_playback_no_active_device() {
+        let (spotify, _) = stub_spotify(StatusCode::NO_CONTENT, "");
+        assert!(spotify.current_playback(None, None).unwrap().is_none());
+        assert!(spotify.current_playing(None, None).unwrap().is_none());
+        assert!(spotify.current_user_playing_track(None).unwrap().is_none());
+        let (spotify, _) = stub_spotify(StatusCode::BAD_GATEWAY, "");
+        assert!(spotify.current_playback(None, None).is_err());
+    }
+
+    #[test]
+    fn test_toggle_nothing_playing() {
+        let (spotify, urls) = stub_spotify(StatusCode::NO_CONTENT, "");
+        assert!(spotify.toggle_current_track_saved().is_err());
+        assert_eq!(urls.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_player_market_param() {
+        let (_, params) = requested(|spotify| {
+            let _ = spotify.current_playback(Some(Country::Sweden), Some(vec![Type::Track]));
+        });
+        assert_eq!(params, vec!["additional_types=track", "market=SE"]);
+    }
+
+    #[test]
+    fn test_playlists_aligned() {
+        let (spotify, urls) = stub_spotify(StatusCode::NOT_FOUND, "");
+        let ids = vec!["spotify:playlist:a".to_owned(), "b".to_owned()];
+        let playlists = spotify.playlists(&ids, Some("name"), None);
+        assert_eq!(playlists.len(), 2);
+        assert!(playlists.iter().all(|playlist| playlist.is_err()));
+        assert_eq!(*urls.lock().unwrap(),
+                   vec!["GET https://api.spotify.com/v1/playlists/a?fields=name&",
+                        "GET https://api.spotify.com/v1/playlists/b?fields=name&"]);
+    }
+
+    #[test]
+    fn test_add_tracks_in_batches() {
+        let (spotify, urls) = stub_spotify(StatusCode::CREATED, r#"{"snapshot_id": "MTIs"}"#);
+        let tracks: Vec<String> = (0..250).map(|i| format!("track{}", i)).collect();
+        let result = spotify.user_playlist_add_tracks("u", "p", &tracks, Some(3)).unwrap();
+        assert_eq!(result.snapshot_id, "MTIs");
+        assert_eq!(urls.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_stop_playback_when_idle() {
+        let (spotify, urls) = stub_spotify(StatusCode::NO_CONTENT, "");
+        assert!(spotify.stop_playback(None).is_ok());
+        assert_eq!(*urls.lock().unwrap(),
+                   vec!["GET https://api.spotify.com/v1/me/player?additional_types=track%2Cepisode&"]);
+    }
+
+    #[test]
+    fn test_queue() {
+        let (spotify, urls) = stub_spotify(StatusCode::OK, r#"{"currently_playing": null, "queue": []}"#);
+        let queue = spotify.queue().unwrap();
+        assert!(queue.currently_playing.is_none());
+        assert!(queue.queue.is_empty());
+        assert_eq!(*urls.lock().unwrap(), vec!["GET https://api.spotify.com/v1/me/player/queue"]);
+    }
+
+    #[test]
+    fn test_seek_to_url() {
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.seek_to(Duration::from_millis(90500), Some("d".to_owned()));
+        });
+        assert_eq!(path, "PUT https://api.spotify.com/v1/me/player/seek");
+        assert_eq!(params, vec!["device_id=d", "position_ms=90500"]);
+    }
+
+    #[test]
+    fn test_retry_only_idempotent_requests() {
+        let urls = Arc::new(Mutex::new(vec![]));
+        let transport = StubTransport {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            body: String::new(),
+            urls: urls.clone(),
+        };
+        let spotify = Spotify::default()
+            .access_token("test-access")
+            .transport(transport)
+            .max_retries(2)
+            .retry_base_delay(Duration::from_millis(0))
+            .build();
+        assert!(spotify.next_track(None).is_err());
+        assert_eq!(urls.lock().unwrap().len(), 1);
+        urls.lock().unwrap().clear();
+        assert!(spotify.me().is_err());
+        assert_eq!(urls.lock().unwrap().len(), 3);
+        urls.lock().unwrap().clear();
+        assert!(spotify.user_playlist_recorder_tracks("u", "p", 0, None, 3, None).is_err());
+        assert_eq!(urls.lock().unwrap().len(), 1);
+        urls.lock().unwrap().clear();
+        assert!(spotify.user_playlist_remove_tracks_by_position("u", "p", &[0], "MTIs").is_err());
+        assert_eq!(urls.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_recently_played_between_url() {
+        let after = Utc.timestamp_millis_opt(1_484_811_043_508).unwrap();
+        let (path, params) = requested(|spotify| {
+            let _ = spotify.current_user_recently_played_between(10, None, Some(after));
+        });
+        assert_eq!(path, "GET https://api.spotify.com/v1/me/player/recently-played");
+        assert_eq!(params, vec!["after=1484811043508", "limit=10"]);
+
+        let (spotify, urls) = stub_spotify(StatusCode::OK, "{}");
+        assert!(spotify.current_user_recently_played_between(10, Some(after), Some(after)).is_err());
+        assert!(urls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reauth_without_refresh_token() {
+        let (spotify, urls) = stub_spotify(StatusCode::UNAUTHORIZED, "");
+        let spotify = spotify.auto_reauth(true);
+        let err = spotify.me().unwrap_err();
+        assert!(matches!(err.downcast_ref::<ApiError>(), Some(ApiError::Unauthorized)));
+        assert_eq!(urls.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_auth_headers_prefer_refreshed_token() {
+        let spotify = Spotify::default().access_token("expired").build();
+        assert_eq!(spotify.auth_headers(), "Bearer expired");
+        let token_info = TokenInfo::default().access_token("refreshed");
+        *spotify.refreshed_token.lock().unwrap() = Some(token_info);
+        assert_eq!(spotify.auth_headers(), "Bearer refreshed");
+    }
+
+    #[test]
+    fn test_recommendations_seed_limit() {
+        let (spotify, urls) = stub_spotify(StatusCode::OK, "{}");
+        let artists: Vec<String> = (0..3).map(|i| format!("artist{}", i)).collect();
+        let tracks: Vec<String> = (0..3).map(|i| format!("track{}", i)).collect();
+        let err = spotify.recommendations(Some(artists), None, Some(tracks), None, None, &Map::new())
+            .unwrap_err();
+        assert!(matches!(err.downcast_ref::<ApiError>(), Some(ApiError::TooManySeeds(6))));
+        let err = spotify.recommendations(None, Some(vec![]), None, None, None, &Map::new())
+            .unwrap_err();
+        assert!(matches!(err.downcast_ref::<ApiError>(), Some(ApiError::NoSeeds)));
+        assert!(urls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_user_agent_header() {
+        let (spotify, headers) = headers_spotify();
+        let _ = spotify.pause_playback(None);
+        let spotify = spotify.user_agent("my-app/1.2");
+        let _ = spotify.pause_playback(None);
+        let user_agents: Vec<String> = headers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|headers| headers[USER_AGENT].to_str().unwrap().to_owned())
+            .collect();
+        assert_eq!(user_agents,
+                   vec![concat!("rspotify/", env!("CARGO_PKG_VERSION")), "my-app/1.2"]);
+    }
+
+    #[test]
+    fn test_available_markets() {
+        let (spotify, urls) = stub_spotify(StatusCode::OK, r#"{"markets": ["SE", "JP"]}"#);
+        assert_eq!(spotify.available_markets().unwrap(), vec!["SE", "JP"]);
+        assert_eq!(*urls.lock().unwrap(), vec!["GET https://api.spotify.com/v1/markets"]);
+    }
+
+    #[test]
+    fn test_token_only_sent_to_api_host() {
+        let (spotify, headers) = headers_spotify();
+        let _ = spotify.get_page::<SavedAlbum>("https://api.spotify.com/v1/me/albums?offset=20");
+        let _ = spotify.get_page::<SavedAlbum>("https://example.com/v1/me/albums?offset=20");
+        let _ = spotify.get_page::<SavedAlbum>("http://api.spotify.com/v1/me/albums?offset=20");
+        let authorized: Vec<bool> = headers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|headers| headers.contains_key(AUTHORIZATION))
+            .collect();
+        assert_eq!(authorized, vec![true, false, false]);
+    }
+
+    #[test]
+    fn test_auto_market_retries_failed_lookup() {
+        let user = include_str!("../../tests/fixtures/private_user.json");
+        let (spotify, urls) = sequence_spotify(vec![(StatusCode::BAD_GATEWAY, ""),
+                                                    (StatusCode::OK, user)]);
+        let spotify = spotify.auto_market(true);
+        assert_eq!(spotify.market_or_default(None), None);
+        assert_eq!(spotify.market_or_default(None), Some(Country::Sweden));
+        // cached after the first successful lookup
+        assert_eq!(spotify.market_or_default(None), Some(Country::Sweden));
+        assert_eq!(urls.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_default_market() {
+        let (spotify, _) = stub_spotify(StatusCode::OK, "{}");
+        let spotify = spotify.market(Country::Sweden);
+        assert_eq!(spotify.market_or_default(None), Some(Country::Sweden));
+        assert_eq!(spotify.market_or_default(Some(Country::Japan)), Some(Country::Japan));
+    }
+
     #[test]
     fn test_get_id() {
         // assert artist
@@ -1779,6 +3663,66 @@ mod tests {
                         "59ZbFPES4DQwEjBpWHzrtC",
                         &spotify.get_id(Type::Playlist, &mut playlist_id)
                 );
+
+        // share link with a tracking query string
+        let track_url = "https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh?si=2bvVlyUUQ5KDR4wYjm0LsA";
+        assert_eq!(
+                        "4iV5W9uYEdYUVa79Axb7Rh",
+                        &spotify.get_id(Type::Track, track_url)
+                );
+
+        // legacy playlist URIs and URLs with the owner's user segment
+        let legacy_uri = "spotify:user:2257tjys2e2u2ygfke42niy2q:playlist:59ZbFPES4DQwEjBpWHzrtC";
+        assert_eq!("59ZbFPES4DQwEjBpWHzrtC", &spotify.get_id(Type::Playlist, legacy_uri));
+        let legacy_url = "https://open.spotify.com/user/2257tjys2e2u2ygfke42niy2q/playlist/59ZbFPES4DQwEjBpWHzrtC";
+        assert_eq!("59ZbFPES4DQwEjBpWHzrtC", &spotify.get_id(Type::Playlist, legacy_url));
+        let modern_url = "https://open.spotify.com/playlist/59ZbFPES4DQwEjBpWHzrtC";
+        assert_eq!("59ZbFPES4DQwEjBpWHzrtC", &spotify.get_id(Type::Playlist, modern_url));
+
+        // the type segment is matched case-insensitively, the id is kept as is
+        assert_eq!("59ZbFPES4DQwEjBpWHzrtC",
+                   &spotify.get_id(Type::Playlist, "spotify:Playlist:59ZbFPES4DQwEjBpWHzrtC"));
+        assert_eq!("4iV5W9uYEdYUVa79Axb7Rh",
+                   &spotify.get_id(Type::Track, "https://open.spotify.com/TRACK/4iV5W9uYEdYUVa79Axb7Rh"));
+    }
+    #[test]
+    fn test_convert_empty_result() {
+        let spotify = Spotify::from_token("test-access");
+        assert!(spotify.convert_result::<()>("").is_ok());
+        assert!(spotify.convert_result::<Option<CUDResult>>("").unwrap().is_none());
+        assert!(spotify.convert_result::<CUDResult>("").is_err());
+    }
+    #[test]
+    fn test_retry_delay() {
+        let spotify = Spotify::default()
+            .access_token("test-access")
+            .retry_base_delay(Duration::from_millis(100))
+            .build();
+        for attempt in 0..4 {
+            let delay = spotify.retry_delay(attempt);
+            let backoff = Duration::from_millis(100 * 2u64.pow(attempt));
+            assert!(delay >= backoff && delay <= backoff + Duration::from_millis(100));
+        }
+    }
+    #[test]
+    fn test_append_params() {
+        let spotify = Spotify::from_token("test-access");
+        let mut params = HashMap::new();
+        assert_eq!("me/tracks", spotify.append_params("me/tracks", &params));
+        params.insert("market", "from_token".to_owned());
+        assert_eq!("me/tracks?market=from_token&",
+                   spotify.append_params("me/tracks", &params));
+        assert_eq!("tracks?ids=a,b&market=from_token&",
+                   spotify.append_params("tracks?ids=a,b", &params));
+    }
+    #[test]
+    fn test_endpoint_url() {
+        let spotify = Spotify::from_token("test-access");
+        assert_eq!("https://api.spotify.com/v1/me", spotify.endpoint_url("me"));
+        assert_eq!("https://api.spotify.com/v1/tracks?ids=a,b", spotify.endpoint_url("/tracks/?ids=a,b"));
+        let spotify = spotify.prefix("http://localhost:1234/v1");
+        assert_eq!("http://localhost:1234/v1/me/tracks", spotify.endpoint_url("me/tracks"));
+        assert_eq!("https://api.spotify.com/v1/me?a=b", spotify.endpoint_url("https://api.spotify.com/v1/me?a=b"));
     }
     #[test]
     fn test_get_uri() {
@@ -1789,5 +3733,52 @@ mod tests {
         let uri2 = spotify.get_uri(Type::Track, track_id2);
         assert_eq!(track_id1,uri1);
         assert_eq!("spotify:track:1301WleyT98MSxVHPZCA6M",&uri2);
+
+        let playlist_uri = "spotify:user:me:playlist:59ZbFPES4DQwEjBpWHzrtC";
+        assert_eq!(playlist_uri, spotify.get_uri(Type::Playlist, playlist_uri));
+    }
+
+    #[test]
+    fn test_is_token_valid_with_access_token() {
+        let user = include_str!("../../tests/fixtures/private_user.json");
+        let (spotify, _) = stub_spotify(StatusCode::OK, user);
+        assert!(spotify.is_token_valid());
+        let (spotify, _) = stub_spotify(StatusCode::UNAUTHORIZED, "");
+        assert!(!spotify.is_token_valid());
+        let (spotify, _) = stub_spotify(StatusCode::BAD_GATEWAY, "");
+        assert!(!spotify.is_token_valid());
+    }
+
+    #[test]
+    fn test_resolve_link() {
+        let track = include_str!("../../tests/fixtures/track.json");
+        let (spotify, urls) = stub_spotify(StatusCode::OK, track);
+        let item = spotify.resolve("https://open.spotify.com/Track/6rqhFgbbKwnb9MLmUQDhG6?si=abc").unwrap();
+        assert!(matches!(item, SpotifyItem::Track(_)));
+        let urls = urls.lock().unwrap();
+        assert!(urls[0].starts_with("GET https://api.spotify.com/v1/tracks/6rqhFgbbKwnb9MLmUQDhG6"),
+                "{}", urls[0]);
+        assert!(!urls[0].contains("si="), "{}", urls[0]);
+    }
+
+    #[test]
+    fn test_from_token() {
+        let spotify = Spotify::from_token("test-access");
+        assert_eq!(spotify.access_token, Some("test-access".to_owned()));
+        assert!(spotify.client_credentials_manager.is_none());
+        assert_eq!(spotify.auth_headers(), "Bearer test-access");
+    }
+
+    #[test]
+    fn test_from_client_credentials() {
+        let client_credentials = SpotifyClientCredentials::default()
+            .client_id("client-id")
+            .client_secret("client-secret")
+            .build();
+        let spotify = Spotify::from_client_credentials(client_credentials);
+        assert!(spotify.access_token.is_none());
+        let manager = spotify.client_credentials_manager.unwrap();
+        assert_eq!(manager.client_id, "client-id");
+        assert_eq!(manager.client_secret, "client-secret");
     }
 }
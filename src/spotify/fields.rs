@@ -0,0 +1,51 @@
+//! Builder for the `fields` filter accepted by the playlist endpoints
+//!
+//! For example
+//! `FieldsBuilder::default().field("name").nested("tracks.items", FieldsBuilder::default().nested("track", FieldsBuilder::default().field("name").field("id"))).build()`
+//! builds `name,tracks.items(track(name,id))`
+
+/// Builds a valid `fields` expression for `user_playlist`,
+/// `user_playlist_tracks` and `playlist`
+#[derive(Clone, Debug, Default)]
+pub struct FieldsBuilder {
+    fields: Vec<String>,
+}
+
+impl FieldsBuilder {
+    /// select a field, dots reach into objects, e.g. `tracks.total`
+    pub fn field(mut self, name: &str) -> FieldsBuilder {
+        self.fields.push(name.to_owned());
+        self
+    }
+
+    /// leave a field out of the response
+    pub fn exclude(mut self, name: &str) -> FieldsBuilder {
+        self.fields.push(format!("!{}", name));
+        self
+    }
+
+    /// select only the given sub fields of `name`
+    pub fn nested(mut self, name: &str, fields: FieldsBuilder) -> FieldsBuilder {
+        self.fields.push(format!("{}({})", name, fields.build()));
+        self
+    }
+
+    pub fn build(&self) -> String {
+        self.fields.join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_fields_builder() {
+        let track = FieldsBuilder::default().field("name").field("id");
+        let fields = FieldsBuilder::default()
+            .field("name")
+            .exclude("description")
+            .nested("tracks.items", FieldsBuilder::default().nested("track", track))
+            .build();
+        assert_eq!(fields, "name,!description,tracks.items(track(name,id))");
+    }
+}
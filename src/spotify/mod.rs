@@ -1,5 +1,8 @@
 pub mod oauth2;
 pub mod client;
+pub mod transport;
 pub mod util;
 pub mod senum;
+pub mod fields;
+pub mod search;
 pub mod model;
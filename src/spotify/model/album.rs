@@ -11,7 +11,7 @@ use super::page::Page;
 
 ///[link to album object simplified](https://developer.spotify.com/web-api/object-model/#album-object-simplified)
 /// Simplified Album Object
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SimplifiedAlbum {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub album_group: Option<String>,
@@ -35,53 +35,131 @@ pub struct SimplifiedAlbum {
     pub uri: Option<String>,
 }
 
+impl SimplifiedAlbum {
+    /// Spotify IDs of all the artists of this album
+    pub fn artist_ids(&self) -> Vec<String> {
+        self.artists.iter().filter_map(|artist| artist.id.clone()).collect()
+    }
 
-/// Restrictions object
-#[derive(Clone, Debug, Serialize, Deserialize)]
+    /// whether both albums are the same release, like
+    /// `FullAlbum::is_same_release_as`. Albums without a release date are
+    /// only the same release when the other one has none either
+    pub fn is_same_release_as(&self, other: &SimplifiedAlbum) -> bool {
+        self.name.trim().to_lowercase() == other.name.trim().to_lowercase()
+            && self.release_date == other.release_date
+            && self.artist_ids() == other.artist_ids()
+    }
+}
+
+/// Restrictions object, e.g. `{"reason": "market"}` when the item is not
+/// available in the requested market
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Restrictions {
     pub reason: String
 }
 
+///[copyright object](https://developer.spotify.com/documentation/web-api/reference/object-model/#copyright-object)
+/// Copyright object, `type` is `C` for the copyright and `P` for the
+/// sound recording (performance) copyright
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Copyright {
+    pub text: String,
+    #[serde(rename = "type")]
+    pub _type: String,
+}
+
 ///[link to album object full](https://developer.spotify.com/web-api/object-model/#album-object-full)
 /// Full Album Object
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FullAlbum {
     pub artists: Vec<SimplifiedArtist>,
     pub album_type: AlbumType,
     pub available_markets: Vec<String>,
-    pub copyrights: Vec<HashMap<String, String>>,
+    pub copyrights: Vec<Copyright>,
     pub external_ids: HashMap<String, String>,
     pub external_urls: HashMap<String, String>,
     pub genres: Vec<String>,
     pub href: String,
     pub id: String,
     pub images: Vec<Image>,
+    pub label: String,
     pub name: String,
     pub popularity: u32,
     pub release_date: String,
     pub release_date_precision: String,
+    #[serde(skip_serializing_if="Option::is_none")]
+    pub restrictions: Option<Restrictions>,
     pub tracks: Page<SimplifiedTrack>,
     #[serde(rename = "type")]
     pub _type: Type,
     pub uri: String,
 }
 
+impl FullAlbum {
+    /// whether the album is available in the given ISO 3166-1 alpha-2
+    /// country code, compared case-insensitively
+    pub fn is_available_in(&self, country: &str) -> bool {
+        self.available_markets
+            .iter()
+            .any(|market| market.eq_ignore_ascii_case(country))
+    }
+
+    /// Spotify IDs of all the artists of this album
+    pub fn artist_ids(&self) -> Vec<String> {
+        self.artists.iter().filter_map(|artist| artist.id.clone()).collect()
+    }
+
+    /// whether both albums are the same release, e.g. the same album
+    /// published separately for different markets. Compares the name
+    /// (case-insensitively), the artists and the release date, and ignores
+    /// the id, markets and everything else that differs between copies
+    pub fn is_same_release_as(&self, other: &FullAlbum) -> bool {
+        self.name.trim().to_lowercase() == other.name.trim().to_lowercase()
+            && self.release_date == other.release_date
+            && self.artist_ids() == other.artist_ids()
+    }
+
+    /// year of `release_date`, which is known whatever its precision
+    pub fn release_year(&self) -> Option<i32> {
+        self.release_date.split('-').next().and_then(|year| year.parse().ok())
+    }
+
+    /// `release_date` as a date, only when `release_date_precision` is
+    /// `day`. Year or month precision dates like `1969` aren't a single day
+    /// and give None, use `release_year` to sort those
+    pub fn release_naive_date(&self) -> Option<NaiveDate> {
+        if self.release_date_precision != "day" {
+            return None;
+        }
+        NaiveDate::parse_from_str(&self.release_date, "%Y-%m-%d").ok()
+    }
+}
+
 /// Full Albums
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FullAlbums {
     pub albums: Vec<FullAlbum>,
 }
 
 ///[link to get list new releases](https://developer.spotify.com/web-api/get-list-new-releases/)
 /// Simplified Albums wrapped by Page object
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct PageSimpliedAlbums {
     pub albums: Page<SimplifiedAlbum>,
 }
 
+/// An artist's albums grouped by type, see `Spotify::artist_discography`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Discography {
+    pub albums: Vec<SimplifiedAlbum>,
+    pub singles: Vec<SimplifiedAlbum>,
+    pub compilations: Vec<SimplifiedAlbum>,
+    pub appears_on: Vec<SimplifiedAlbum>,
+}
+
 ///[link to save album object](https://developer.spotify.com/web-api/object-model/#save-album-object)
 /// Saved Album object
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SavedAlbum {
     pub added_at: DateTime<Utc>,
     pub album: FullAlbum,
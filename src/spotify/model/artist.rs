@@ -1,13 +1,13 @@
 //! All objects related to artist defined by Spotify API
 
 use std::collections::HashMap;
-use serde_json::Value;
 use spotify::senum::Type;
 use super::image::Image;
+use super::followers::Followers;
 use super::page::CursorBasedPage;
 ///[artist object simplified](https://developer.spotify.com/web-api/object-model/#artist-object-simplified)
 /// Simplified Artist Object
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SimplifiedArtist {
     pub external_urls: HashMap<String, String>,
     pub href: Option<String>,
@@ -20,10 +20,10 @@ pub struct SimplifiedArtist {
 
 ///[artist object full](https://developer.spotify.com/web-api/object-model/#artist-object-full)
 /// Full Artist Object
-#[derive(Clone, Debug,Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FullArtist {
     pub external_urls: HashMap<String, String>,
-    pub followers: HashMap<String, Option<Value>>,
+    pub followers: Followers,
     pub genres: Vec<String>,
     pub href: String,
     pub id: String,
@@ -36,13 +36,13 @@ pub struct FullArtist {
 }
 
 /// Full artist vector
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FullArtists {
     pub artists: Vec<FullArtist>,
 }
 
 /// Full Artists vector wrapped by cursor-based-page object
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct CursorPageFullArtists {
     pub artists: CursorBasedPage<FullArtist>,
 }
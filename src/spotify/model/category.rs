@@ -3,7 +3,7 @@ use super::image::Image;
 use super::page::Page;
 /// category object
 ///[category object](https://developer.spotify.com/web-api/get-list-categories/#categoryobject)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Category {
     pub href: String,
     pub icons: Vec<Image>,
@@ -13,7 +13,7 @@ pub struct Category {
 
 /// Categories wrapped by page object
 ///[get list categories](https://developer.spotify.com/web-api/get-list-categories/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct PageCategory {
     pub categories: Page<Category>,
 }
@@ -1,12 +1,12 @@
 //! All objects related to context
 use std::collections::HashMap;
 
-use spotify::senum::{Type,RepeatState};
+use spotify::senum::{Type, RepeatState, CurrentlyPlayingType};
 use super::device::Device;
-use super::track::FullTrack;
+use super::playing::PlayingItem;
 /// Context object
 ///[get the users currently playing track](https://developer.spotify.com/web-api/get-the-users-currently-playing-track/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Context {
     pub uri: String,
     pub href: String,
@@ -17,7 +17,7 @@ pub struct Context {
 
 /// Full playing context
 ///[get information about the users current playback](https://developer.spotify.com/web-api/get-information-about-the-users-current-playback/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FullPlayingContext {
     pub device: Device,
     pub repeat_state: RepeatState,
@@ -26,17 +26,83 @@ pub struct FullPlayingContext {
     pub timestamp: u64,
     pub progress_ms: Option<u32>,
     pub is_playing: bool,
-    pub item: Option<FullTrack>,
+    pub item: Option<PlayingItem>,
+    pub currently_playing_type: CurrentlyPlayingType,
 }
 
 
 
 ///[get the users currently playing track](https://developer.spotify.com/web-api/get-the-users-currently-playing-track/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SimplifiedPlayingContext {
     pub context: Option<Context>,
     pub timestamp: u64,
     pub progress_ms: Option<u32>,
     pub is_playing: bool,
-    pub item: Option<FullTrack>,
+    pub item: Option<PlayingItem>,
+    pub currently_playing_type: CurrentlyPlayingType,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    #[test]
+    fn test_playing_context_with_episode() {
+        let json = r#"{
+            "context": null,
+            "timestamp": 1571220000000,
+            "progress_ms": 42000,
+            "is_playing": true,
+            "currently_playing_type": "episode",
+            "item": {
+                "audio_preview_url": null,
+                "description": "An episode",
+                "duration_ms": 1800000,
+                "explicit": false,
+                "external_urls": {},
+                "href": "https://api.spotify.com/v1/episodes/512ojhOuo1ktJprKbVcKyQ",
+                "id": "512ojhOuo1ktJprKbVcKyQ",
+                "images": [],
+                "is_externally_hosted": false,
+                "is_playable": true,
+                "languages": ["en"],
+                "name": "Episode",
+                "release_date": "2019-10-01",
+                "release_date_precision": "day",
+                "show": {
+                    "available_markets": [],
+                    "copyrights": [],
+                    "description": "A show",
+                    "explicit": false,
+                    "external_urls": {},
+                    "href": "https://api.spotify.com/v1/shows/38bS44xjbVVZ3No3ByF1dJ",
+                    "id": "38bS44xjbVVZ3No3ByF1dJ",
+                    "images": [],
+                    "is_externally_hosted": false,
+                    "languages": ["en"],
+                    "media_type": "audio",
+                    "name": "Show",
+                    "publisher": "Publisher",
+                    "type": "show",
+                    "uri": "spotify:show:38bS44xjbVVZ3No3ByF1dJ"
+                },
+                "type": "episode",
+                "uri": "spotify:episode:512ojhOuo1ktJprKbVcKyQ"
+            }
+        }"#;
+        let playing: SimplifiedPlayingContext = serde_json::from_str(json).unwrap();
+        assert_eq!(playing.currently_playing_type, CurrentlyPlayingType::Episode);
+        match playing.item {
+            Some(PlayingItem::Episode(episode)) => assert_eq!(episode.duration_ms, 1800000),
+            _ => panic!("expected an episode"),
+        }
+    }
+    #[test]
+    fn test_playing_item_unknown_type() {
+        let json = r#"{"context": null, "timestamp": 1, "progress_ms": null, "is_playing": true,
+                       "currently_playing_type": "ad", "item": {"type": "ad"}}"#;
+        let err = serde_json::from_str::<SimplifiedPlayingContext>(json).unwrap_err();
+        assert!(err.to_string().contains("unknown variant `ad`"), "{}", err);
+    }
 }
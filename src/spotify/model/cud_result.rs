@@ -1,5 +1,9 @@
 //! the result of post/put/delete request  
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Returned by every request that changes the tracks of a playlist. Pass
+/// `snapshot_id` to a following `user_playlist_recorder_tracks` or
+/// `user_playlist_remove_*` call so it is applied to the version of the
+/// playlist you have seen, instead of clobbering concurrent edits.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct CUDResult {
     pub snapshot_id: String,
 }
@@ -1,7 +1,7 @@
 /// All objects related to device
 ///[get a users available devices](https://developer.spotify.com/web-api/get-a-users-available-devices/)
 use spotify::senum::DeviceType;
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Device {
     pub id: String,
     pub is_active: bool,
@@ -12,7 +12,7 @@ pub struct Device {
     pub volume_percent: u32,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct DevicePayload {
     pub devices: Vec<Device>,
 }
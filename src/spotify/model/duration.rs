@@ -0,0 +1,53 @@
+//! Duration in milliseconds, as the API reports them
+use std::fmt;
+use std::time::Duration;
+
+/// A duration in milliseconds such as a track's `duration_ms`, serialized as
+/// the plain integer the API uses. Displays as `m:ss`, or `h:mm:ss` from one
+/// hour on, with the seconds truncated like Spotify's own clients do
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct DurationMs(pub u32);
+
+impl DurationMs {
+    pub fn as_millis(self) -> u32 {
+        self.0
+    }
+
+    pub fn to_std_duration(self) -> Duration {
+        Duration::from_millis(u64::from(self.0))
+    }
+}
+
+impl From<u32> for DurationMs {
+    fn from(millis: u32) -> Self {
+        DurationMs(millis)
+    }
+}
+
+impl fmt::Display for DurationMs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let seconds = self.0 / 1000;
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        if hours > 0 {
+            write!(f, "{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            write!(f, "{}:{:02}", minutes, seconds)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_duration_ms() {
+        assert_eq!(DurationMs(242_373).to_string(), "4:02");
+        assert_eq!(DurationMs(59_999).to_string(), "0:59");
+        assert_eq!(DurationMs(3_723_000).to_string(), "1:02:03");
+        assert_eq!(DurationMs(1500).to_std_duration(), Duration::from_millis(1500));
+        let parsed: DurationMs = serde_json::from_str("242373").unwrap();
+        assert_eq!(parsed, DurationMs(242_373));
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), "242373");
+    }
+}
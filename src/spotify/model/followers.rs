@@ -0,0 +1,8 @@
+//! Followers object
+///[followers object](https://developer.spotify.com/documentation/web-api/reference/object-model/#followers-object)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Followers {
+    /// always null, the Web API doesn't support it yet
+    pub href: Option<String>,
+    pub total: u32,
+}
@@ -1,6 +1,6 @@
 //! Image object
 ///[image object](https://developer.spotify.com/web-api/object-model/#image-object)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Image {
     pub height: Option<u32>,
     pub url: String,
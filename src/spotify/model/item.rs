@@ -0,0 +1,18 @@
+//! Any object a spotify URI or URL can point to
+use super::album::FullAlbum;
+use super::artist::FullArtist;
+use super::playlist::FullPlaylist;
+use super::show::{FullEpisode, FullShow};
+use super::track::FullTrack;
+use super::user::PublicUser;
+/// Object resolved from a spotify URI or URL, see `Spotify::resolve`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub enum SpotifyItem {
+    Track(Box<FullTrack>),
+    Album(Box<FullAlbum>),
+    Artist(Box<FullArtist>),
+    Playlist(Box<FullPlaylist>),
+    User(Box<PublicUser>),
+    Show(Box<FullShow>),
+    Episode(Box<FullEpisode>),
+}
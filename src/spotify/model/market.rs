@@ -0,0 +1,6 @@
+//! Markets object
+///[get available markets](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-available-markets)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Markets {
+    pub markets: Vec<String>,
+}
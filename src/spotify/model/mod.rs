@@ -1,11 +1,13 @@
 //! All Spotify API endpoint response object
 pub mod artist;
 pub mod image;
+pub mod followers;
 pub mod album;
 pub mod track;
 pub mod page;
 pub mod user;
 pub mod playlist;
+pub mod show;
 pub mod cud_result;
 pub mod context;
 pub mod playing;
@@ -15,3 +17,6 @@ pub mod audio;
 pub mod device;
 pub mod search;
 pub mod offset;
+pub mod item;
+pub mod market;
+pub mod duration;
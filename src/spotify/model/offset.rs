@@ -1,6 +1,6 @@
 //! Offset object
 ///[offset object](https://developer.spotify.com/documentation/web-api/reference/player/start-a-users-playback/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Offset {
     pub position: Option<u32>,
     pub uri: Option<String>
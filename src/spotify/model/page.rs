@@ -1,7 +1,7 @@
 //! All kinds of page object
 ///Basic page
 ///ppaging abject(https://developer.spotify.com/web-api/object-model/#paging-object)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Page<T> {
     pub href: String,
     pub items: Vec<T>,
@@ -11,9 +11,24 @@ pub struct Page<T> {
     pub previous: Option<String>,
     pub total: u32,
 }
+
+impl<T> Page<T> {
+    /// whether there is a page after this one
+    pub fn has_next(&self) -> bool {
+        self.next.is_some()
+    }
+    /// whether there is a page before this one
+    pub fn has_previous(&self) -> bool {
+        self.previous.is_some()
+    }
+    /// zero-based index of this page, computed from `offset` and `limit`
+    pub fn page_number(&self) -> u32 {
+        self.offset.checked_div(self.limit).unwrap_or(0)
+    }
+}
 /// cursor based page
 ///[cursor based paging object](https://developer.spotify.com/web-api/object-model/#cursor-based-paging-object)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct CursorBasedPage<T> {
     pub href: String,
     pub items: Vec<T>,
@@ -26,7 +41,27 @@ pub struct CursorBasedPage<T> {
 }
 ///Cursor object
 ///[cursor object](https://developer.spotify.com/web-api/object-model/#cursor-object)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Cursor {
     pub after: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_page_navigation() {
+        let page: Page<u32> = Page {
+            href: String::from("https://api.spotify.com/v1/me/tracks?offset=40&limit=20"),
+            items: vec![],
+            limit: 20,
+            next: Some(String::from("https://api.spotify.com/v1/me/tracks?offset=60&limit=20")),
+            offset: 40,
+            previous: None,
+            total: 65,
+        };
+        assert!(page.has_next());
+        assert!(!page.has_previous());
+        assert_eq!(page.page_number(), 2);
+    }
+}
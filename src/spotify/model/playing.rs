@@ -1,23 +1,67 @@
 //! All kinds of play object
 use chrono::prelude::*;
+use serde::de::{self, Deserialize, Deserializer};
+use serde_json::{self, Value};
 
+use spotify::senum::CurrentlyPlayingType;
 use super::context::Context;
+use super::show::FullEpisode;
 use super::track::FullTrack;
 use super::track::SimplifiedTrack;
+/// The track or podcast episode being played, told apart by its `type`
+/// field like `currently_playing_type` does for the whole response
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum PlayingItem {
+    Track(FullTrack),
+    Episode(FullEpisode),
+}
+// not `#[serde(tag = "type")]`, the tracks and episodes keep their own
+// `type` field
+impl<'de> Deserialize<'de> for PlayingItem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<PlayingItem, D::Error> {
+        let item = Value::deserialize(deserializer)?;
+        let item_type = item.get("type").and_then(Value::as_str).map(str::to_owned);
+        match item_type.as_deref() {
+            Some("track") => serde_json::from_value(item).map(PlayingItem::Track).map_err(de::Error::custom),
+            Some("episode") => serde_json::from_value(item).map(PlayingItem::Episode).map_err(de::Error::custom),
+            Some(other) => Err(de::Error::unknown_variant(other, &["track", "episode"])),
+            None => Err(de::Error::missing_field("type")),
+        }
+    }
+}
+impl PlayingItem {
+    /// length of the track or episode in milliseconds
+    pub fn duration_ms(&self) -> u32 {
+        match *self {
+            PlayingItem::Track(ref track) => track.duration_ms.as_millis(),
+            PlayingItem::Episode(ref episode) => episode.duration_ms,
+        }
+    }
+}
 /// current playing track
 ///[get the users currently playing track](https://developer.spotify.com/web-api/get-the-users-currently-playing-track/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Playing {
     pub context: Option<Context>,
     pub timestamp: u64,
     pub progress_ms: Option<u32>,
     pub is_playing: bool,
-    pub item: Option<FullTrack>,
+    pub item: Option<PlayingItem>,
+    pub currently_playing_type: CurrentlyPlayingType,
+}
+
+/// the user's playback queue
+///[get the users queue](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-queue)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Queue {
+    pub currently_playing: Option<PlayingItem>,
+    pub queue: Vec<PlayingItem>,
 }
 
 /// playing history
 ///[play history object](https://developer.spotify.com/web-api/object-model/#play-history-object)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct PlayHistory {
     pub track: SimplifiedTrack,
     pub played_at: DateTime<Utc>,
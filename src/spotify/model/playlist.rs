@@ -4,12 +4,13 @@ use chrono::prelude::*;
 use std::collections::HashMap;
 
 use super::image::Image;
+use super::followers::Followers;
 use super::user::PublicUser;
 use super::track::FullTrack;
 use super::page::Page;
 use spotify::senum::Type;
 ///[playlist object simplified](https://developer.spotify.com/web-api/object-model/#playlist-object-simplified)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SimplifiedPlaylist {
     pub collaborative: bool,
     pub external_urls: HashMap<String, String>,
@@ -26,12 +27,12 @@ pub struct SimplifiedPlaylist {
     pub uri: String,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FullPlaylist {
     pub collaborative: bool,
     pub description: String,
     pub external_urls: HashMap<String, String>,
-    pub followers: Option<HashMap<String, Value>>,
+    pub followers: Option<Followers>,
     pub href: String,
     pub id: String,
     pub images: Vec<Image>,
@@ -45,17 +46,76 @@ pub struct FullPlaylist {
     pub uri: String,
 }
 
+impl FullPlaylist {
+    /// total duration in milliseconds of the tracks contained in this
+    /// object. Spotify only embeds the first page of tracks, fetch the
+    /// remaining pages with `user_playlist_tracks` for longer playlists
+    pub fn total_duration_ms(&self) -> u64 {
+        self.tracks.total_duration_ms()
+    }
+
+    /// the tracks of the embedded first page, skipping unavailable ones
+    pub fn tracks_flat(&self) -> Vec<&FullTrack> {
+        self.tracks
+            .items
+            .iter()
+            .filter_map(|item| item.track.as_ref())
+            .collect()
+    }
+}
+
 ///[playlist track object](https://developer.spotify.com/web-api/object-model/#playlist-track-object)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct PlaylistTrack {
     pub added_at: DateTime<Utc>,
     pub added_by: Option<PublicUser>,
     pub is_local: bool,
-    pub track: FullTrack,
+    /// null when the track is no longer available, e.g. it was removed
+    /// from Spotify or is a local file that can't be resolved
+    pub track: Option<FullTrack>,
+}
+
+impl Page<PlaylistTrack> {
+    /// tracks that pass an explicit-content filter: every track when `allow`
+    /// is true, otherwise only the tracks not marked as explicit
+    pub fn filter_explicit(&self, allow: bool) -> Vec<&FullTrack> {
+        self.items
+            .iter()
+            .filter_map(|item| item.track.as_ref())
+            .filter(|track| allow || !track.explicit)
+            .collect()
+    }
+
+    /// total duration in milliseconds of the tracks in this page, entries
+    /// without a track are skipped
+    pub fn total_duration_ms(&self) -> u64 {
+        self.items
+            .iter()
+            .filter_map(|item| item.track.as_ref())
+            .map(|track| u64::from(track.duration_ms.as_millis()))
+            .sum()
+    }
 }
 ///[get list featured playlists](https://developer.spotify.com/web-api/get-list-featured-playlists/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FeaturedPlaylists {
     pub message: String,
     pub playlists: Page<SimplifiedPlaylist>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    #[test]
+    fn test_playlist_track_without_track() {
+        let json = r#"{
+            "added_at": "2019-10-11T08:00:00Z",
+            "added_by": null,
+            "is_local": true,
+            "track": null
+        }"#;
+        let playlist_track: PlaylistTrack = serde_json::from_str(json).unwrap();
+        assert!(playlist_track.track.is_none());
+    }
+}
@@ -1,13 +1,13 @@
 //! All objects related to recommendation
 use super::track::SimplifiedTrack;
 ///[recommendations object](https://developer.spotify.com/web-api/object-model/#recommendations-object)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Recommendations {
     pub seeds: Vec<RecommendationsSeed>,
     pub tracks: Vec<SimplifiedTrack>,
 }
 ///[recommendations seed object](https://developer.spotify.com/web-api/object-model/#recommendations-seed-object)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct RecommendationsSeed {
     #[serde(rename = "afterFilteringSize")]
     pub after_filtering_size: u32,
@@ -21,7 +21,7 @@ pub struct RecommendationsSeed {
     pub _type: RecommendationsSeedType,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum RecommendationsSeedType {
     #[serde(rename = "ARTIST")]
     Artist,
@@ -30,3 +30,9 @@ pub enum RecommendationsSeedType {
     #[serde(rename = "GENRE")]
     Genre,
 }
+
+///[available genre seeds](https://developer.spotify.com/documentation/web-api/reference/browse/get-recommendations/)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GenreSeeds {
+    pub genres: Vec<String>,
+}
@@ -5,23 +5,23 @@ use super::artist::FullArtist;
 use super::track::FullTrack;
 use super::playlist::SimplifiedPlaylist;
 ///[search item](https://developer.spotify.com/web-api/search-item/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SearchPlaylists {
     pub playlists: Page<SimplifiedPlaylist>,
 }
 ///[search item](https://developer.spotify.com/web-api/search-item/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SearchAlbums {
     pub albums: Page<SimplifiedAlbum>,
 }
 
 ///[search item](https://developer.spotify.com/web-api/search-item/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SearchArtists {
     pub artists: Page<FullArtist>,
 }
 ///[search item](https://developer.spotify.com/web-api/search-item/)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SearchTracks {
     pub tracks: Page<FullTrack>,
 }
@@ -0,0 +1,126 @@
+//! All objects related to podcast shows and their episodes
+use chrono::prelude::*;
+
+use std::collections::HashMap;
+
+use spotify::senum::Type;
+use super::album::Copyright;
+use super::image::Image;
+use super::page::Page;
+
+///[simplified show object](https://developer.spotify.com/documentation/web-api/reference/object-model/#show-object-simplified)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SimplifiedShow {
+    pub available_markets: Vec<String>,
+    pub copyrights: Vec<Copyright>,
+    pub description: String,
+    pub explicit: bool,
+    pub external_urls: HashMap<String, String>,
+    pub href: String,
+    pub id: String,
+    pub images: Vec<Image>,
+    pub is_externally_hosted: Option<bool>,
+    pub languages: Vec<String>,
+    pub media_type: String,
+    pub name: String,
+    pub publisher: String,
+    #[serde(rename = "type")]
+    pub _type: Type,
+    pub uri: String,
+}
+
+///[full show object](https://developer.spotify.com/documentation/web-api/reference/object-model/#show-object-full)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FullShow {
+    pub available_markets: Vec<String>,
+    pub copyrights: Vec<Copyright>,
+    pub description: String,
+    pub episodes: Page<SimplifiedEpisode>,
+    pub explicit: bool,
+    pub external_urls: HashMap<String, String>,
+    pub href: String,
+    pub id: String,
+    pub images: Vec<Image>,
+    pub is_externally_hosted: Option<bool>,
+    pub languages: Vec<String>,
+    pub media_type: String,
+    pub name: String,
+    pub publisher: String,
+    #[serde(rename = "type")]
+    pub _type: Type,
+    pub uri: String,
+}
+
+///[saved show object](https://developer.spotify.com/documentation/web-api/reference/object-model/#saved-show-object)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SavedShow {
+    pub added_at: DateTime<Utc>,
+    pub show: SimplifiedShow,
+}
+
+///[simplified episode object](https://developer.spotify.com/documentation/web-api/reference/object-model/#episode-object-simplified)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SimplifiedEpisode {
+    pub audio_preview_url: Option<String>,
+    pub description: String,
+    pub duration_ms: u32,
+    pub explicit: bool,
+    pub external_urls: HashMap<String, String>,
+    pub href: String,
+    pub id: String,
+    pub images: Vec<Image>,
+    pub is_externally_hosted: bool,
+    pub is_playable: bool,
+    #[serde(default)]
+    pub languages: Vec<String>,
+    pub name: String,
+    pub release_date: String,
+    pub release_date_precision: String,
+    /// only present when the access token belongs to a user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resume_point: Option<ResumePoint>,
+    #[serde(rename = "type")]
+    pub _type: Type,
+    pub uri: String,
+}
+
+///[full episode object](https://developer.spotify.com/documentation/web-api/reference/object-model/#episode-object-full)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FullEpisode {
+    pub audio_preview_url: Option<String>,
+    pub description: String,
+    pub duration_ms: u32,
+    pub explicit: bool,
+    pub external_urls: HashMap<String, String>,
+    pub href: String,
+    pub id: String,
+    pub images: Vec<Image>,
+    pub is_externally_hosted: bool,
+    pub is_playable: bool,
+    #[serde(default)]
+    pub languages: Vec<String>,
+    pub name: String,
+    pub release_date: String,
+    pub release_date_precision: String,
+    /// only present when the access token belongs to a user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resume_point: Option<ResumePoint>,
+    pub show: SimplifiedShow,
+    #[serde(rename = "type")]
+    pub _type: Type,
+    pub uri: String,
+}
+
+/// Full episodes object wrapped by `Vec`
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FullEpisodes {
+    pub episodes: Vec<FullEpisode>,
+}
+
+///[resume point object](https://developer.spotify.com/documentation/web-api/reference/object-model/#resume-point-object)
+/// Where the user stopped listening to an episode
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ResumePoint {
+    pub fully_played: bool,
+    pub resume_position_ms: u32,
+}
@@ -1,21 +1,26 @@
 //! All kinds of tracks object
 use chrono::prelude::*;
+use failure;
+use reqwest::Client;
 
+use std::cmp::Reverse;
 use std::collections::HashMap;
 
 use super::artist::SimplifiedArtist;
 use super::album::SimplifiedAlbum;
 use super::album::Restrictions;
+use super::page::Page;
+use super::duration::DurationMs;
 use spotify::senum::Type;
 ///[track object full](https://developer.spotify.com/web-api/object-model/#track-object-full)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FullTrack {
     pub album: SimplifiedAlbum,
     pub artists: Vec<SimplifiedArtist>,
     #[serde(skip_serializing_if="Vec::is_empty",default)]
     pub available_markets: Vec<String>,
     pub disc_number: i32,
-    pub duration_ms: u32,
+    pub duration_ms: DurationMs,
     pub explicit: bool,
     pub external_ids: HashMap<String, String>,
     pub external_urls: HashMap<String, String>,
@@ -37,10 +42,42 @@ pub struct FullTrack {
     pub uri: String,
 }
 
+impl FullTrack {
+    /// whether the track is available in the given ISO 3166-1 alpha-2
+    /// country code, compared case-insensitively
+    pub fn is_available_in(&self, country: &str) -> bool {
+        self.available_markets
+            .iter()
+            .any(|market| market.eq_ignore_ascii_case(country))
+    }
+
+    /// Spotify IDs of all the artists of this track, skipping local artists
+    /// that have none
+    pub fn artist_ids(&self) -> Vec<String> {
+        self.artists.iter().filter_map(|artist| artist.id.clone()).collect()
+    }
+
+    /// download the 30 second MP3 preview behind `preview_url`, pass
+    /// `&CLIENT` or the `Spotify` client to reuse its connection pool
+    pub fn preview_bytes(&self, client: &Client) -> Result<Vec<u8>, failure::Error> {
+        let url = match self.preview_url {
+            Some(ref url) => url,
+            None => return Err(format_err!("track {:?} has no preview_url", self.name)),
+        };
+        let mut response = client.get(url.as_str()).send()?;
+        if !response.status().is_success() {
+            return Err(format_err!("preview download failed with status {}", response.status()));
+        }
+        let mut bytes = Vec::new();
+        response.copy_to(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
 /// [link to track link] https://developer.spotify.com/documentation/web-api/reference/object-model/#track-link
 /// Track Link
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct TrackLink{
     pub external_urls: HashMap<String, String>,
     pub href: String,
@@ -50,23 +87,52 @@ pub struct TrackLink{
     pub uri: String
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FullTracks {
     pub tracks: Vec<FullTrack>,
 }
+
+impl FullTracks {
+    /// tracks that pass an explicit-content filter: every track when `allow`
+    /// is true, otherwise only the tracks not marked as explicit
+    pub fn filter_explicit(&self, allow: bool) -> Vec<&FullTrack> {
+        self.tracks
+            .iter()
+            .filter(|track| allow || !track.explicit)
+            .collect()
+    }
+
+    /// references to the tracks sorted by popularity, leaving `tracks`
+    /// untouched. Tracks with equal popularity keep their original order
+    pub fn sorted_by_popularity(&self, descending: bool) -> Vec<&FullTrack> {
+        let mut tracks: Vec<&FullTrack> = self.tracks.iter().collect();
+        if descending {
+            tracks.sort_by_key(|track| Reverse(track.popularity));
+        } else {
+            tracks.sort_by_key(|track| track.popularity);
+        }
+        tracks
+    }
+}
 ///[track object simplified](https://developer.spotify.com/web-api/object-model/#track-object-simplified)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SimplifiedTrack {
     pub artists: Vec<SimplifiedArtist>,
     pub available_markets: Option<Vec<String>>,
     pub disc_number: i32,
-    pub duration_ms: u32,
+    pub duration_ms: DurationMs,
     pub explicit: bool,
     pub external_urls: HashMap<String, String>,
     #[serde(default)]
     pub href: Option<String>,
     pub id: Option<String>,
     pub is_local: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_playable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linked_from: Option<TrackLink>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restrictions>,
     pub name: String,
     pub preview_url: Option<String>,
     pub track_number: u32,
@@ -75,8 +141,18 @@ pub struct SimplifiedTrack {
     pub uri: String,
 }
 
+impl Page<SimplifiedTrack> {
+    /// tracks of this page in album order, by disc number and then by track
+    /// number, so that multi-disc albums list correctly
+    pub fn sorted_by_disc_and_track(&self) -> Vec<&SimplifiedTrack> {
+        let mut tracks: Vec<&SimplifiedTrack> = self.items.iter().collect();
+        tracks.sort_by_key(|track| (track.disc_number, track.track_number));
+        tracks
+    }
+}
+
 ///[saved track object](https://developer.spotify.com/web-api/object-model/#saved-track-object)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SavedTrack {
     pub added_at: DateTime<Utc>,
     pub track: FullTrack,
@@ -1,17 +1,17 @@
 //! All kinds of user object
-use serde_json::Value;
 use chrono::NaiveDate;
 
 use std::collections::HashMap;
 
 use super::image::Image;
+use super::followers::Followers;
 use spotify::senum::Type;
 ///[public user object](https://developer.spotify.com/web-api/object-model/#user-object-public)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct PublicUser {
     pub display_name: Option<String>,
     pub external_urls: HashMap<String, String>,
-    pub followers: Option<HashMap<String, Option<Value>>>,
+    pub followers: Option<Followers>,
     pub href: String,
     pub id: String,
     pub images: Option<Vec<Image>>,
@@ -21,14 +21,14 @@ pub struct PublicUser {
 }
 
 ///[private user object](https://developer.spotify.com/web-api/object-model/#user-object-private)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct PrivateUser {
     pub birthdate: Option<NaiveDate>,
     pub country: Option<String>,
     pub display_name: Option<String>,
     pub email: Option<String>,
     pub external_urls: HashMap<String, String>,
-    pub followers: Option<HashMap<String, Option<Value>>>,
+    pub followers: Option<Followers>,
     pub href: String,
     pub id: String,
     pub images: Option<Vec<Image>>,
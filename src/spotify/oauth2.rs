@@ -4,7 +4,6 @@ use chrono::prelude::*;
 use serde_json;
 use reqwest::Client;
 use dotenv::dotenv;
-use percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
 
 // use built-in library
 use std::env;
@@ -163,6 +162,14 @@ impl SpotifyClientCredentials {
             }
         }
     }
+    /// whether an access token is available: the cached token info hasn't
+    /// expired yet, or a new token can be fetched with the client credentials
+    pub fn is_token_valid(&self) -> bool {
+        match self.token_info {
+            Some(ref token_info) if !self.is_token_expired(token_info) => true,
+            _ => self.request_access_token().is_some(),
+        }
+    }
     fn is_token_expired(&self, token_info: &TokenInfo) -> bool {
         is_token_expired(token_info)
     }
@@ -363,8 +370,7 @@ impl SpotifyOAuth {
 
         let query_str = convert_map_to_string(&payload);
         let mut authorize_url = String::from("https://accounts.spotify.com/authorize?");
-        authorize_url
-            .push_str(&utf8_percent_encode(&query_str, PATH_SEGMENT_ENCODE_SET).to_string());
+        authorize_url.push_str(&query_str);
         trace!("{:?}", &authorize_url);
         authorize_url
     }
@@ -532,7 +538,7 @@ mod tests {
             .build();
         match spotify_oauth.parse_response_code(&mut url) {
             Some(code) => assert_eq!(code, "AQD0yXvFEOvw"),
-            None => println!("failed"),
+            None => panic!("failed to parse the response code"),
         }
     }
 }
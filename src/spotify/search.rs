@@ -0,0 +1,101 @@
+//! Builder for the `q` parameter of the search endpoints
+//!
+//! For example
+//! `SearchQuery::default().keyword("blue").artist("Miles Davis").years(1955, 1960).build()`
+//! builds `blue artist:"Miles Davis" year:1955-1960`
+
+/// Builds a search query with field filters for `search_album`,
+/// `search_artist`, `search_track` and `search_playlist`
+#[derive(Clone, Debug, Default)]
+pub struct SearchQuery {
+    terms: Vec<String>,
+}
+
+impl SearchQuery {
+    /// match a free keyword in any field
+    pub fn keyword(mut self, keyword: &str) -> SearchQuery {
+        self.terms.push(keyword.to_owned());
+        self
+    }
+
+    /// leave out results matching the keyword
+    pub fn exclude(mut self, keyword: &str) -> SearchQuery {
+        self.terms.push(format!("NOT {}", keyword));
+        self
+    }
+
+    pub fn album(self, album: &str) -> SearchQuery {
+        self.filter("album", album)
+    }
+
+    pub fn artist(self, artist: &str) -> SearchQuery {
+        self.filter("artist", artist)
+    }
+
+    pub fn track(self, track: &str) -> SearchQuery {
+        self.filter("track", track)
+    }
+
+    /// only artists and tracks of this genre
+    pub fn genre(self, genre: &str) -> SearchQuery {
+        self.filter("genre", genre)
+    }
+
+    pub fn isrc(self, isrc: &str) -> SearchQuery {
+        self.filter("isrc", isrc)
+    }
+
+    pub fn upc(self, upc: &str) -> SearchQuery {
+        self.filter("upc", upc)
+    }
+
+    pub fn year(self, year: u32) -> SearchQuery {
+        self.filter("year", &year.to_string())
+    }
+
+    /// released between the two years, both included
+    pub fn years(self, from: u32, to: u32) -> SearchQuery {
+        self.filter("year", &format!("{}-{}", from, to))
+    }
+
+    /// only albums released in the past two weeks
+    pub fn tag_new(self) -> SearchQuery {
+        self.filter("tag", "new")
+    }
+
+    /// only albums with the lowest 10% popularity
+    pub fn tag_hipster(self) -> SearchQuery {
+        self.filter("tag", "hipster")
+    }
+
+    /// values with spaces are quoted so the filter applies to all words
+    fn filter(mut self, name: &str, value: &str) -> SearchQuery {
+        if value.contains(char::is_whitespace) {
+            self.terms.push(format!("{}:\"{}\"", name, value));
+        } else {
+            self.terms.push(format!("{}:{}", name, value));
+        }
+        self
+    }
+
+    /// the query, not yet URL-encoded, the search methods encode it
+    pub fn build(&self) -> String {
+        self.terms.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_search_query() {
+        let query = SearchQuery::default()
+            .keyword("blue")
+            .artist("Miles Davis")
+            .years(1955, 1960)
+            .tag_new()
+            .exclude("live")
+            .build();
+        assert_eq!(query, "blue artist:\"Miles Davis\" year:1955-1960 tag:new NOT live");
+    }
+}
@@ -1,7 +1,10 @@
 //! All enums for rspotify
+use std::convert::TryFrom;
 use std::fmt;
+
+use failure;
 /// Album type - ‘album’, ‘single’, ‘appears_on’, ‘compilation’
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum AlbumType {
     Album,
@@ -28,6 +31,17 @@ impl AlbumType {
         }
     }
 }
+/// Parse an album type such as `single` or `appears_on`, failing with an
+/// error that lists the accepted values on unknown input
+impl<'a> TryFrom<&'a str> for AlbumType {
+    type Error = failure::Error;
+    fn try_from(s: &'a str) -> Result<AlbumType, failure::Error> {
+        AlbumType::from_str(s).ok_or_else(|| {
+            format_err!("unknown album type {:?}, expected one of: album, single, appears_on, compilation",
+                        s)
+        })
+    }
+}
 impl fmt::Debug for AlbumType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -39,7 +53,7 @@ impl fmt::Debug for AlbumType {
     }
 }
 
-///  Type: ‘artist’, ‘album’,‘track’ or ‘playlist’
+///  Type: ‘artist’, ‘album’, ‘track’, ‘playlist’, ‘user’, ‘show’ or ‘episode’
 #[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum Type {
@@ -48,6 +62,8 @@ pub enum Type {
     Track,
     Playlist,
     User,
+    Show,
+    Episode,
 }
 
 impl Type {
@@ -58,6 +74,8 @@ impl Type {
             "track" => Some(Type::Track),
             "playlist" => Some(Type::Playlist),
             "user" => Some(Type::User),
+            "show" => Some(Type::Show),
+            "episode" => Some(Type::Episode),
             _ => None,
         }
     }
@@ -68,6 +86,8 @@ impl Type {
             Type::Track => "track",
             Type::Playlist => "playlist",
             Type::User => "user",
+            Type::Show => "show",
+            Type::Episode => "episode",
         }
     }
 }
@@ -80,12 +100,14 @@ impl fmt::Debug for Type {
             Type::Track => write!(f, "track"),
             Type::Playlist => write!(f, "playlist"),
             Type::User => write!(f, "user"),
+            Type::Show => write!(f, "show"),
+            Type::Episode => write!(f, "episode"),
         }
     }
 }
 
 /// time range: long-term, medium-term, short-term
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum TimeRange {
     LongTerm,
@@ -122,7 +144,11 @@ impl fmt::Debug for TimeRange {
 }
 ///ISO 3166-1 alpha-2 country code, [wiki about ISO 3166-1](https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2)
 ///Source from [country-list](https://datahub.io/core/country-list)
-#[derive(Clone, Serialize, Deserialize)]
+///
+///Used for every `market`/`country` parameter, `FromToken` picks the
+///country of the user the access token belongs to. Parse user input with
+///`Country::try_from` to reject codes such as "UK" (use "GB") up front.
+#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
 pub enum Country {
     Afghanistan,
     AlandIslands,
@@ -373,6 +399,9 @@ pub enum Country {
     Yemen,
     Zambia,
     Zimbabwe,
+    /// Not a real country: tells Spotify to use the country associated with
+    /// the user's access token, which is how track relinking is applied.
+    FromToken,
 }
 impl Country {
     pub fn from_str(s: &str) -> Option<Country> {
@@ -626,6 +655,7 @@ impl Country {
             "YE" => Some(Country::Yemen),
             "ZM" => Some(Country::Zambia),
             "ZW" => Some(Country::Zimbabwe),
+            "from_token" => Some(Country::FromToken),
 
             _ => None,
         }
@@ -881,10 +911,24 @@ impl Country {
             Country::Yemen => "YE",
             Country::Zambia => "ZM",
             Country::Zimbabwe => "ZW",
+            Country::FromToken => "from_token",
 
         }
     }
 }
+impl<'a> TryFrom<&'a str> for Country {
+    type Error = failure::Error;
+    fn try_from(s: &'a str) -> Result<Country, failure::Error> {
+        match Country::from_str(s) {
+            Some(country) => Ok(country),
+            None if s == "UK" => Err(format_err!("unknown market \"UK\", the ISO 3166-1 code of the United Kingdom is \"GB\"")),
+            None => Err(format_err!(
+                "unknown market {:?}, expected an ISO 3166-1 alpha-2 country code or from_token",
+                s
+            )),
+        }
+    }
+}
 impl fmt::Debug for Country {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -1137,6 +1181,7 @@ impl fmt::Debug for Country {
             Country::Yemen => write!(f, "YE"),
             Country::Zambia => write!(f, "ZM"),
             Country::Zimbabwe => write!(f, "ZW"),
+            Country::FromToken => write!(f, "from_token"),
 
         }
     }
@@ -1146,7 +1191,7 @@ impl fmt::Debug for Country {
 /// - track will repeat the current track.
 /// - context will repeat the current context.
 /// - off will turn repeat off. 
-#[derive(Clone, Debug,Copy, Serialize, Deserialize)]
+#[derive(Clone, Debug, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum RepeatState {
     Off,
@@ -1171,6 +1216,26 @@ impl RepeatState {
     }
 }
 
+/// Type of the item currently playing: track, episode, ad or unknown
+#[derive(Clone, Debug, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CurrentlyPlayingType {
+    Track,
+    Episode,
+    Ad,
+    Unknown,
+}
+impl CurrentlyPlayingType {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            CurrentlyPlayingType::Track => "track",
+            CurrentlyPlayingType::Episode => "episode",
+            CurrentlyPlayingType::Ad => "ad",
+            CurrentlyPlayingType::Unknown => "unknown",
+        }
+    }
+}
+
 /// Type for search: artist, album, track, playlist 
 #[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1217,7 +1282,7 @@ impl fmt::Debug for SearchType {
 /// See the [Spotify developer
 /// docs](https://developer.spotify.com/documentation/web-api/reference/player/get-a-users-available-devices/#device-types)
 /// for more information, or in case we are missing a device type here.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum DeviceType {
     Computer,
     Tablet,
@@ -1233,3 +1298,21 @@ pub enum DeviceType {
     Automobile,
     Unknown,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_album_type_from_str() {
+        assert_eq!(AlbumType::from_str("single").unwrap().as_str(), "single");
+        assert!(AlbumType::from_str("ep").is_none());
+        assert_eq!(AlbumType::try_from("appears_on").unwrap().as_str(), "appears_on");
+        assert!(AlbumType::try_from("ep").is_err());
+    }
+    #[test]
+    fn test_country_try_from() {
+        assert!(Country::try_from("GB").unwrap() == Country::UnitedKingdom);
+        assert!(Country::try_from("from_token").unwrap() == Country::FromToken);
+        assert!(Country::try_from("UK").is_err());
+    }
+}
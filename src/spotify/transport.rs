@@ -0,0 +1,53 @@
+//! HTTP layer used by the client, swap it out to test without the network
+use failure;
+use reqwest::{Client, Method, StatusCode};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+use std::io::Read;
+
+/// Response returned by a `HttpTransport`
+#[derive(Clone, Debug)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+/// Sends the requests of a `Spotify` client. `reqwest::Client` is the
+/// default, tests can inject a stub that returns canned JSON with
+/// `Spotify::transport`
+pub trait HttpTransport: Send + Sync {
+    fn call(&self,
+            method: &Method,
+            url: &str,
+            body: Option<&Value>,
+            headers: &HeaderMap)
+            -> Result<TransportResponse, failure::Error>;
+}
+
+impl HttpTransport for Client {
+    fn call(&self,
+            method: &Method,
+            url: &str,
+            body: Option<&Value>,
+            headers: &HeaderMap)
+            -> Result<TransportResponse, failure::Error> {
+        let builder = self.request(method.clone(), url).headers(headers.clone());
+        // only add body if necessary
+        // spotify rejects GET requests that have a body with a 400 response
+        let builder = if let Some(json) = body {
+            builder.json(json)
+        } else {
+            builder
+        };
+        let mut response = builder.send()?;
+        let mut buf = String::new();
+        response.read_to_string(&mut buf)?;
+        Ok(TransportResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: buf,
+        })
+    }
+}
@@ -3,6 +3,7 @@ use rand::{self, Rng};
 use rand::distributions::Alphanumeric;
 use chrono::prelude::*;
 use webbrowser;
+use url::form_urlencoded;
 
 use std::io;
 use std::string::ToString;
@@ -30,14 +31,15 @@ pub fn generate_random_string(length: usize) -> String {
 /// to
 /// `redirect_uri=my_uri&state=my-state&scope=test-scope`
 /// Since hashmap is not sorted, so the order of key-value-pairs
-/// may differ from times
+/// may differ from times. Keys and values are percent-encoded as UTF-8, so
+/// non-ASCII text such as `東京` or `🎵` is safe to pass
 pub fn convert_map_to_string<K: Debug + Eq + Hash+ ToString,
 V: Debug+ToString>(map: &HashMap<K, V>) -> String{
     let mut string: String = String::new();
     for (key, value) in map.iter() {
-        string.push_str(&key.to_string());
+        string.extend(form_urlencoded::byte_serialize(key.to_string().as_bytes()));
         string.push_str("=");
-        string.push_str(&value.to_string());
+        string.extend(form_urlencoded::byte_serialize(value.to_string().as_bytes()));
         string.push_str("&");
     }
     string
@@ -67,7 +69,11 @@ pub fn request_token(spotify_oauth: &mut SpotifyOAuth) {
     let state = generate_random_string(16);
     let auth_url = spotify_oauth.get_authorize_url(Some(&state), None);
     match webbrowser::open(&auth_url) {
+        Ok(_) if cfg!(feature = "quiet") => info!("Opened {} in your browser", auth_url),
         Ok(_) => println!("Opened {} in your browser", auth_url),
+        Err(why) if cfg!(feature = "quiet") => {
+            error!("Error {:?};Please navigate here [{:?}] ", why, auth_url)
+        }
         Err(why) => eprintln!("Error {:?};Please navigate here [{:?}] ", why, auth_url),
     }
 }
@@ -85,6 +91,7 @@ pub fn get_token(spotify_oauth: &mut SpotifyOAuth) -> Option<TokenInfo> {
         Some(token_info) => Some(token_info),
         None => {
             request_token(spotify_oauth);
+            // an interactive prompt, not a diagnostic, so `quiet` keeps it
             println!("Enter the URL you were redirected to: ");
             let mut input = String::new();
             match io::stdin().read_line(&mut input) {
@@ -128,4 +135,12 @@ mod tests {
         assert!(result.contains("state=my-state&"));
         assert!(result.contains("scope=test-scope&"));
     }
+    #[test]
+    fn test_convert_map_to_string_non_ascii() {
+        let mut map = HashMap::new();
+        map.insert("q", "東京 & مرحبا 🎵");
+        let result = convert_map_to_string(&map);
+        assert_eq!(result,
+                   "q=%E6%9D%B1%E4%BA%AC+%26+%D9%85%D8%B1%D8%AD%D8%A8%D8%A7+%F0%9F%8E%B5&");
+    }
 }
@@ -0,0 +1,157 @@
+extern crate chrono;
+extern crate rspotify;
+extern crate serde;
+extern crate serde_json;
+
+use chrono::NaiveDate;
+use rspotify::spotify::model::album::FullAlbum;
+use rspotify::spotify::model::artist::FullArtist;
+use rspotify::spotify::model::context::FullPlayingContext;
+use rspotify::spotify::model::playing::PlayingItem;
+use rspotify::spotify::model::playlist::FullPlaylist;
+use rspotify::spotify::model::show::{FullEpisode, SimplifiedEpisode};
+use rspotify::spotify::model::track::FullTrack;
+use rspotify::spotify::model::user::PrivateUser;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use std::fmt::Debug;
+
+/// every non-null key of `serialized` must exist in `original`, so a
+/// misnamed field shows up instead of silently dropping the API's data.
+/// Optional fields the API left out serialize as null and are fine
+fn assert_keys_exist(serialized: &Value, original: &Value, path: &str) {
+    match (serialized, original) {
+        (Value::Object(serialized), Value::Object(original)) => {
+            for (key, value) in serialized {
+                let path = format!("{}.{}", path, key);
+                match original.get(key) {
+                    Some(original) => assert_keys_exist(value, original, &path),
+                    None if value.is_null() => {}
+                    None => panic!("{} is not in the API response", path),
+                }
+            }
+        }
+        (Value::Array(serialized), Value::Array(original)) => {
+            for (i, (value, original)) in serialized.iter().zip(original).enumerate() {
+                assert_keys_exist(value, original, &format!("{}[{}]", path, i));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// deserialize the fixture, serialize it again and check that nothing got
+/// renamed and that the second round trip gives the same model
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(fixture: &str) -> T {
+    let original: Value = serde_json::from_str(fixture).unwrap();
+    let model: T = serde_json::from_value(original.clone()).unwrap();
+    let serialized = serde_json::to_value(&model).unwrap();
+    assert_keys_exist(&serialized, &original, "");
+    let reparsed: T = serde_json::from_value(serialized).unwrap();
+    assert_eq!(model, reparsed);
+    model
+}
+
+#[test]
+fn test_track_round_trip() {
+    let track: FullTrack = round_trip(include_str!("fixtures/track.json"));
+    assert_eq!(track.name, "Speak Softly Love");
+    assert_eq!(track.album.release_date, Some("2011-11-07".to_owned()));
+    assert_eq!(track.artist_ids(), vec!["2WX2uTcsvV5OnS0inACecP"]);
+}
+
+#[test]
+fn test_album_round_trip() {
+    let album: FullAlbum = round_trip(include_str!("fixtures/album.json"));
+    assert_eq!(album.label, "Warner Music UK Ltd");
+    assert_eq!(album.tracks.items.len(), 1);
+    assert_eq!(album.artist_ids(), vec!["2WX2uTcsvV5OnS0inACecP"]);
+
+    let mut tracks = album.tracks.clone();
+    let mut bonus = tracks.items[0].clone();
+    bonus.disc_number = 2;
+    bonus.track_number = 1;
+    tracks.items.insert(0, bonus);
+    let sorted = tracks.sorted_by_disc_and_track();
+    assert_eq!(sorted[0].disc_number, 1);
+    assert_eq!(sorted[1].disc_number, 2);
+}
+
+#[test]
+fn test_album_same_release() {
+    let album: FullAlbum = serde_json::from_str(include_str!("fixtures/album.json")).unwrap();
+    let mut copy = album.clone();
+    copy.id = "4aawyAB9vmqN3uQ7FjRGTy".to_owned();
+    copy.name = album.name.to_uppercase();
+    copy.available_markets = vec!["US".to_owned()];
+    assert!(album.is_same_release_as(&copy));
+
+    copy.release_date = "2012-01-01".to_owned();
+    assert!(!album.is_same_release_as(&copy));
+}
+
+#[test]
+fn test_simplified_album_same_release() {
+    let track: FullTrack = serde_json::from_str(include_str!("fixtures/track.json")).unwrap();
+    let album = track.album;
+    let mut copy = album.clone();
+    copy.id = Some("4aawyAB9vmqN3uQ7FjRGTy".to_owned());
+    copy.name = album.name.to_uppercase();
+    assert!(album.is_same_release_as(&copy));
+
+    copy.release_date = Some("2012-01-01".to_owned());
+    assert!(!album.is_same_release_as(&copy));
+}
+
+#[test]
+fn test_album_release_date() {
+    let mut album: FullAlbum = serde_json::from_str(include_str!("fixtures/album.json")).unwrap();
+    assert_eq!(album.release_year(), Some(2011));
+    assert_eq!(album.release_naive_date(), Some(NaiveDate::from_ymd_opt(2011, 11, 7).unwrap()));
+
+    album.release_date = "1969".to_owned();
+    album.release_date_precision = "year".to_owned();
+    assert_eq!(album.release_year(), Some(1969));
+    assert_eq!(album.release_naive_date(), None);
+}
+
+#[test]
+fn test_artist_round_trip() {
+    let artist: FullArtist = round_trip(include_str!("fixtures/artist.json"));
+    assert_eq!(artist.followers.total, 3532651);
+}
+
+#[test]
+fn test_playlist_round_trip() {
+    let playlist: FullPlaylist = round_trip(include_str!("fixtures/playlist.json"));
+    assert!(playlist.tracks.items[0].track.is_none());
+    let track = playlist.tracks.items[1].track.as_ref().unwrap();
+    assert_eq!(track.name, "Speak Softly Love");
+}
+
+#[test]
+fn test_episode_round_trip() {
+    let episode: FullEpisode = round_trip(include_str!("fixtures/episode.json"));
+    assert_eq!(episode.show.publisher, "Sveriges Radio");
+    assert_eq!(episode.resume_point.as_ref().map(|point| point.fully_played), Some(false));
+
+    let simplified: SimplifiedEpisode = serde_json::from_str(include_str!("fixtures/episode.json")).unwrap();
+    assert_eq!(simplified.resume_point, episode.resume_point);
+}
+
+#[test]
+fn test_current_playback_round_trip() {
+    let playback: FullPlayingContext = round_trip(include_str!("fixtures/current_playback.json"));
+    match playback.item {
+        Some(PlayingItem::Track(track)) => assert_eq!(track.id.as_ref().unwrap(), "6rqhFgbbKwnb9MLmUQDhG6"),
+        _ => panic!("expected a track"),
+    }
+}
+
+#[test]
+fn test_private_user_round_trip() {
+    let user: PrivateUser = round_trip(include_str!("fixtures/private_user.json"));
+    assert_eq!(user.country, Some("SE".to_owned()));
+}
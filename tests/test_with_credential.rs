@@ -5,6 +5,7 @@ extern crate lazy_static;
 use rspotify::spotify::client::Spotify;
 use rspotify::spotify::oauth2::SpotifyClientCredentials;
 use rspotify::spotify::senum::{AlbumType, Country};
+use rspotify::spotify::model::item::SpotifyItem;
 
 use std::sync::Mutex;
 
@@ -52,10 +53,30 @@ fn test_album_tracks() {
         .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
         .build();
     let birdy_uri = "spotify:album:6akEvsycLGftJxYudPjmqK";
-    let tracks = spotify.album_track(birdy_uri, Some(2), None);
+    let tracks = spotify.album_track(birdy_uri, Some(2), None, None);
     assert!(tracks.is_ok());
 }
 
+#[test]
+fn test_album_tracks_all() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+    let birdy_uri = "spotify:album:6akEvsycLGftJxYudPjmqK";
+    let first_page = spotify.album_track(birdy_uri, Some(1), None, None).unwrap();
+    let tracks = spotify.album_tracks_all(birdy_uri, None).unwrap();
+    assert_eq!(tracks.len() as u32, first_page.total);
+}
+
+#[test]
+fn test_available_markets() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+    let markets = spotify.available_markets().unwrap();
+    assert!(markets.iter().any(|market| market == "US"));
+}
+
 #[test]
 fn test_artist_related_artists() {
     
@@ -94,6 +115,43 @@ fn test_artists_albums() {
     assert!(albums.is_ok());
 }
 
+#[test]
+fn test_artist_albums_all() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+    let birdy_uri = "spotify:artist:2WX2uTcsvV5OnS0inACecP";
+    let first_page = spotify.artist_albums(birdy_uri, None, Some(Country::UnitedStates), Some(1), None).unwrap();
+    let albums = spotify.artist_albums_all(birdy_uri, None, Some(Country::UnitedStates), None).unwrap();
+    assert_eq!(albums.len() as u32, first_page.total);
+}
+
+#[test]
+fn test_artist_discography() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+    let birdy_uri = "spotify:artist:2WX2uTcsvV5OnS0inACecP";
+    let discography = spotify.artist_discography(birdy_uri, Some(Country::UnitedStates)).unwrap();
+    let albums = spotify.artist_albums_all(birdy_uri, Some(AlbumType::Album), Some(Country::UnitedStates), None)
+        .unwrap();
+    assert_eq!(discography.albums, albums);
+}
+
+#[test]
+fn test_artist_albums_all_dedup() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+    let birdy_uri = "spotify:artist:2WX2uTcsvV5OnS0inACecP";
+    let albums = spotify.artist_albums_all(birdy_uri, None, None, true).unwrap();
+    let mut ids: Vec<_> = albums.iter().map(|album| album.id.clone()).collect();
+    let total = ids.len();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), total);
+}
+
 #[test]
 fn test_artists() {
     
@@ -119,6 +177,16 @@ fn test_artist_top_tracks() {
     assert!(tracks.is_ok());
 }
 
+#[test]
+fn test_artist_top_tracks_limit() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+    let birdy_uri = "spotify:artist:2WX2uTcsvV5OnS0inACecP";
+    let tracks = spotify.artist_top_tracks_limited(birdy_uri, Country::UnitedStates, 3).unwrap();
+    assert!(tracks.tracks.len() <= 3);
+}
+
 #[test]
 fn test_audio_analysis() {
     
@@ -216,3 +284,89 @@ fn test_fake_playlist() {
     let playlist = spotify.playlist("fake_id", None, None);
     assert!(!playlist.is_ok());
 }
+
+#[test]
+fn test_playlist_audio_features() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+
+    let pairs = spotify.playlist_audio_features("spotify", "37i9dQZF1DZ06evO45P0Eo");
+    assert!(pairs.is_ok());
+    for (track, features) in pairs.unwrap() {
+        assert_eq!(track.id.unwrap(), features.id);
+    }
+}
+
+#[test]
+fn test_resolve() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+
+    let item = spotify.resolve("https://open.spotify.com/track/6rqhFgbbKwnb9MLmUQDhG6?si=abc");
+    match item {
+        Ok(SpotifyItem::Track(track)) => assert_eq!(track.id.unwrap(), "6rqhFgbbKwnb9MLmUQDhG6"),
+        _ => panic!("expected a track"),
+    }
+}
+
+#[test]
+fn test_check_genre_seeds() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+
+    assert!(spotify.check_genre_seeds(&["rock".to_owned()]).is_ok());
+    assert!(spotify.check_genre_seeds(&["not-a-genre".to_owned()]).is_err());
+}
+
+#[test]
+fn test_is_token_valid() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+
+    assert!(spotify.is_token_valid());
+}
+
+#[test]
+fn test_show() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+
+    let show = spotify.show("spotify:show:38bS44xjbVVZ3No3ByF1dJ", Some(Country::UnitedStates));
+    assert!(show.is_ok());
+}
+
+#[test]
+fn test_show_episodes() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+
+    let episodes = spotify.show_episodes("38bS44xjbVVZ3No3ByF1dJ", 10, 0, Some(Country::UnitedStates));
+    assert!(episodes.is_ok());
+}
+
+#[test]
+fn test_episode() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+
+    let episode = spotify.episode("spotify:episode:512ojhOuo1ktJprKbVcKyQ", Some(Country::Spain));
+    assert!(episode.is_ok());
+}
+
+#[test]
+fn test_episodes() {
+    let spotify = Spotify::default()
+        .client_credentials_manager(CLIENT_CREDENTIAL.lock().unwrap().clone())
+        .build();
+
+    let ids = vec!["77o6BIVlYM3msb4MMIL1jH".to_owned(), "0Q86acNRm6V9GYx55SXKwf".to_owned()];
+    let episodes = spotify.episodes(ids, Some(Country::UnitedStates));
+    assert_eq!(episodes.unwrap().len(), 2);
+}
@@ -42,13 +42,33 @@ fn test_current_playback() {
             let spotify = Spotify::default()
                 .client_credentials_manager(client_credential)
                 .build();
-            let context = spotify.current_playback(None);
+            let context = spotify.current_playback(None, None);
             assert!(context.is_ok());
         }
         None => assert!(false),
     };
 
 }
+#[test]#[ignore]
+fn test_queue() {
+    let mut oauth = SpotifyOAuth::default()
+        .scope("user-read-playback-state")
+        .build();
+    match get_token(&mut oauth) {
+        Some(token_info) => {
+            let client_credential = SpotifyClientCredentials::default()
+                .token_info(token_info)
+                .build();
+            let spotify = Spotify::default()
+                .client_credentials_manager(client_credential)
+                .build();
+            let queue = spotify.queue();
+            assert!(queue.is_ok());
+        }
+        None => panic!("failed to get a token"),
+    };
+}
+
 #[test]#[ignore]
 fn test_current_playing() {
     let mut oauth = SpotifyOAuth::default()
@@ -62,7 +82,7 @@ fn test_current_playing() {
             let spotify = Spotify::default()
                 .client_credentials_manager(client_credential)
                 .build();
-            let context = spotify.current_playing(None);
+            let context = spotify.current_playing(None, None);
             assert!(context.is_ok());
         }
         None => assert!(false),
@@ -101,7 +121,7 @@ fn test_current_user_playing_track() {
             let spotify = Spotify::default()
                 .client_credentials_manager(client_credential)
                 .build();
-            let playing = spotify.current_user_playing_track();
+            let playing = spotify.current_user_playing_track(None);
             assert!(playing.is_ok())
         }
         None => assert!(false),
@@ -212,6 +232,27 @@ fn test_current_user_saved_albums() {
     };
 }
 
+#[test]#[ignore]
+fn test_toggle_current_track_saved() {
+    let mut oauth = SpotifyOAuth::default()
+        .scope("user-read-currently-playing user-library-read user-library-modify")
+        .build();
+    match get_token(&mut oauth) {
+        Some(token_info) => {
+            let client_credential = SpotifyClientCredentials::default()
+                .token_info(token_info)
+                .build();
+            let spotify = Spotify::default()
+                .client_credentials_manager(client_credential)
+                .build();
+            let saved = spotify.toggle_current_track_saved().unwrap();
+            // toggle back to leave the library as it was
+            assert_eq!(spotify.toggle_current_track_saved().unwrap(), !saved);
+        }
+        None => panic!("failed to get a token"),
+    };
+}
+
 #[test]#[ignore]
 fn test_current_user_saved_tracks_add() {
     let mut oauth = SpotifyOAuth::default().scope("user-library-modify").build();
@@ -259,6 +300,48 @@ fn test_current_user_saved_tracks_contains() {
 
 }
 
+#[test]#[ignore]
+fn test_current_user_saved_tracks_contains_batched() {
+    let mut oauth = SpotifyOAuth::default().scope("user-library-read").build();
+    match get_token(&mut oauth) {
+        Some(token_info) => {
+            let client_credential = SpotifyClientCredentials::default()
+                .token_info(token_info)
+                .build();
+            let spotify = Spotify::default()
+                .client_credentials_manager(client_credential)
+                .build();
+            let tracks_ids = vec![String::from("spotify:track:4iV5W9uYEdYUVa79Axb7Rh"); 120];
+            let result = spotify.current_user_saved_tracks_contains(&tracks_ids);
+            assert_eq!(result.unwrap().len(), 120);
+        }
+        None => panic!("failed to get a token"),
+    };
+}
+
+#[test]#[ignore]
+fn test_saved_shows() {
+    let mut oauth = SpotifyOAuth::default()
+        .scope("user-library-read user-library-modify")
+        .build();
+    match get_token(&mut oauth) {
+        Some(token_info) => {
+            let client_credential = SpotifyClientCredentials::default()
+                .token_info(token_info)
+                .build();
+            let spotify = Spotify::default()
+                .client_credentials_manager(client_credential)
+                .build();
+            let show_ids = vec![String::from("spotify:show:38bS44xjbVVZ3No3ByF1dJ")];
+            assert!(spotify.save_shows(&show_ids).is_ok());
+            assert_eq!(spotify.check_saved_shows(&show_ids).unwrap(), vec![true]);
+            assert!(spotify.get_saved_shows(10, 0).is_ok());
+            assert!(spotify.remove_saved_shows(&show_ids, None).is_ok());
+        }
+        None => panic!("failed to get a token"),
+    };
+}
+
 #[test]#[ignore]
 fn test_current_user_saved_tracks_delete() {
     let mut oauth = SpotifyOAuth::default().scope("user-library-modify").build();
@@ -558,7 +641,7 @@ fn test_search_artist() {
                 .client_credentials_manager(client_credential)
                 .build();
             let query = "tania bowra";
-            let result = spotify.search_artist(query, 10, 0, Some(Country::UnitedStates));
+            let result = spotify.search_artist(query, 10, 0, Some(Country::UnitedStates), None);
             assert!(result.is_ok());
         }
         None => assert!(false),
@@ -666,6 +749,28 @@ fn test_start_playback() {
     };
 }
 #[test]#[ignore]
+fn test_start_context_playback() {
+    let mut oauth = SpotifyOAuth::default()
+        .scope("user-modify-playback-state")
+        .build();
+    match get_token(&mut oauth) {
+        Some(token_info) => {
+            let client_credential = SpotifyClientCredentials::default()
+                .token_info(token_info)
+                .build();
+            let spotify = Spotify::default()
+                .client_credentials_manager(client_credential)
+                .build();
+            let device_id = String::from("74ASZWbe4lXaubB36ztrGX");
+            let result = spotify.start_context_playback("spotify:album:0sNOF9WDwhWunNAHPD3Baj",
+                                                        Some(device_id),
+                                                        for_position(2));
+            assert!(result.is_ok());
+        }
+        None => panic!("failed to get a token"),
+    };
+}
+#[test]#[ignore]
 fn test_transfer_playback() {
     let mut oauth = SpotifyOAuth::default()
         .scope("user-modify-playback-state")
@@ -875,7 +980,7 @@ fn test_user_playlist_create() {
                 .build();
             let user_id = "2257tjys2e2u2ygfke42niy2q";
             let playlist_name = "A New Playlist";
-            let playlists = spotify.user_playlist_create(user_id, playlist_name, false, None);
+            let playlists = spotify.user_playlist_create(user_id, playlist_name, false, None, None);
             assert!(playlists.is_ok());
 
         }
@@ -1008,6 +1113,32 @@ fn test_user_playlist_remove_specific_occurrenes_of_tracks() {
 
 }
 
+#[test]#[ignore]
+fn test_user_playlist_remove_tracks_by_position() {
+    let mut oauth = SpotifyOAuth::default()
+        .scope("playlist-modify-private playlist-modify-public")
+        .build();
+    match get_token(&mut oauth) {
+        Some(token_info) => {
+            let client_credential = SpotifyClientCredentials::default()
+                .token_info(token_info)
+                .build();
+            let spotify = Spotify::default()
+                .client_credentials_manager(client_credential)
+                .build();
+            let user_id = "2257tjys2e2u2ygfke42niy2q";
+            let playlist_id = "5jAOgWXCBKuinsGiZxjDQ5";
+            let playlist = spotify.playlist(playlist_id, None, None).unwrap();
+            let result = spotify.user_playlist_remove_tracks_by_position(user_id,
+                                                                         playlist_id,
+                                                                         &[0],
+                                                                         &playlist.snapshot_id);
+            assert!(result.is_ok());
+        }
+        None => panic!("failed to get a token"),
+    };
+}
+
 #[test]#[ignore]
 fn test_user_playlist_replace_tracks() {
     let mut oauth = SpotifyOAuth::default()